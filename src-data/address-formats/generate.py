@@ -0,0 +1,52 @@
+import csv
+import json
+import sys
+
+FIELDS = {
+    'N': 'Name',
+    'O': 'Organization',
+    'A': 'StreetAddress',
+    'D': 'DependentLocality',
+    'C': 'Locality',
+    'S': 'AdministrativeArea',
+    'Z': 'PostalCode',
+    'X': 'SortingCode'
+}
+
+
+def enum_name(value):
+    return ''.join(part.capitalize() for part in value.split('_'))
+
+
+def read_data():
+    formats = {}
+    with open('formats.csv', newline='', encoding='utf-8') as csv_file:
+        rows = list(csv.DictReader(csv_file))
+    default = rows[0]
+    for row in rows:
+        for key in default:
+            if not row[key]:
+                row[key] = default[key]
+        formats[row['alpha_2']] = {
+            'format': row['format'],
+            'required': [FIELDS[field] for field in row['require']],
+            'upper': [FIELDS[field] for field in row['upper']],
+            'administrative_area_name': enum_name(row['state_name_type']),
+            'locality_name': enum_name(row['locality_name_type']),
+            'dependent_locality_name': enum_name(row['sublocality_name_type']),
+            'postal_code_name': enum_name(row['zip_name_type'])
+        }
+    return formats
+
+
+def write_data(file_name, data, out_path):
+    print('writing %s/%s' % (out_path, file_name))
+    with open('%s/%s' % (out_path, file_name), 'w') as text_file:
+        json.dump(data, text_file, ensure_ascii=False, separators=(',', ':'))
+        print('', file=text_file)
+
+
+if len(sys.argv) < 2:
+    print('Error: need a path argument')
+else:
+    write_data('address_formats.json', read_data(), sys.argv[1])
@@ -0,0 +1,46 @@
+import csv
+import json
+import sys
+
+
+def read_registry(out_path, file_name):
+    with open('%s/%s' % (out_path, file_name)) as json_file:
+        return json.load(json_file)
+
+
+def read_data(out_path):
+    languages = read_registry(out_path, 'languages.json')
+    country_codes = set(
+        country['short_code'] for country in read_registry(out_path, 'countries.json').values()
+    )
+    aliases = {'language': {}, 'script': {}, 'region': {}}
+    with open('aliases.csv', newline='') as csv_file:
+        for row in csv.DictReader(csv_file):
+            alias = row['alias']
+            replacement = row['replacement']
+            if row['kind'] == 'language':
+                # shortening a 3-character code to the 2-character code for
+                # the same language is done using the language registry.
+                if alias in languages and languages[alias]['short_code'] == replacement:
+                    continue
+                aliases['language'][alias] = replacement
+            elif row['kind'] == 'script':
+                aliases['script'][alias] = replacement
+            else:
+                regions = [region for region in replacement.split(' ') if region in country_codes]
+                if regions:
+                    aliases['region'][alias] = regions
+    return aliases
+
+
+def write_data(file_name, data, out_path):
+    print('writing %s/%s' % (out_path, file_name))
+    with open('%s/%s' % (out_path, file_name), 'w') as text_file:
+        json.dump(data, text_file, ensure_ascii=False, separators=(',', ':'), sort_keys=True)
+        print('', file=text_file)
+
+
+if len(sys.argv) < 2:
+    print('Error: need a path argument')
+else:
+    write_data('aliases.json', read_data(sys.argv[1]), sys.argv[1])
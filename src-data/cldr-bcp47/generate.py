@@ -0,0 +1,39 @@
+import csv
+import json
+import sys
+
+
+def read_data():
+    keys = {}
+    with open('keys.csv', newline='') as csv_file:
+        for row in csv.DictReader(csv_file):
+            keys[row['key']] = {
+                'key': row['key'],
+                'description': row['description'],
+                'value_type': row['value_type'],
+                'types': [],
+                'aliases': {},
+                'deprecated': [],
+            }
+    with open('types.csv', newline='') as csv_file:
+        for row in csv.DictReader(csv_file):
+            key = keys[row['key']]
+            key['types'].append(row['type'])
+            if row['alias']:
+                key['aliases'][row['alias']] = row['type']
+            if row['deprecated'] == 'true':
+                key['deprecated'].append(row['type'])
+    return keys
+
+
+def write_data(file_name, data, out_path):
+    print('writing %s/%s' % (out_path, file_name))
+    with open('%s/%s' % (out_path, file_name), 'w') as text_file:
+        json.dump(data, text_file, ensure_ascii=False, separators=(',', ':'), sort_keys=True)
+        print('', file=text_file)
+
+
+if len(sys.argv) < 2:
+    print('Error: need a path argument')
+else:
+    write_data('unicode_extension_keys.json', read_data(), sys.argv[1])
@@ -0,0 +1,82 @@
+import csv
+import json
+import sys
+
+
+def read_registry(out_path, file_name):
+    with open('%s/%s' % (out_path, file_name)) as json_file:
+        return json.load(json_file)
+
+
+def read_data(out_path):
+    languages = read_registry(out_path, 'languages.json')
+    codes = {
+        'language': set(languages.keys()) | set(
+            language['short_code'] for language in languages.values() if language['short_code']
+        ),
+        'script': set(read_registry(out_path, 'scripts.json').keys()),
+        'territory': set(
+            country['short_code'] for country in read_registry(out_path, 'countries.json').values()
+        ) | set('%03d' % int(code) for code in read_registry(out_path, 'regions.json').keys()),
+        'currency': set(read_registry(out_path, 'currencies.json').keys()),
+    }
+    tables = {
+        ('language', ''): 'languages',
+        ('language', 'short'): 'short_languages',
+        ('dialect', ''): 'dialects',
+        ('dialect', 'short'): 'short_dialects',
+        ('script', ''): 'scripts',
+        ('territory', ''): 'territories',
+        ('territory', 'short'): 'short_territories',
+        ('variant', ''): 'variants',
+        ('currency', ''): 'currencies',
+    }
+
+    def is_valid(kind, code):
+        if kind == 'dialect':
+            language, *rest = code.split('_')
+            return language in codes['language'] and all(
+                part in codes['script'] or part in codes['territory'] for part in rest
+            )
+        elif kind == 'variant':
+            return 4 <= len(code) <= 8 and code.isalnum()
+        elif kind == 'pattern':
+            return code in ('pattern', 'separator')
+        else:
+            return code in codes[kind]
+
+    names = {}
+    skipped = 0
+    with open('display_names.csv', newline='') as csv_file:
+        for row in csv.DictReader(csv_file):
+            kind, code, form = row['kind'], row['code'], row['form']
+            if not is_valid(kind, code):
+                skipped += 1
+                continue
+            locale = names.setdefault(row['locale'].replace('_', '-'), dict(
+                [(table, {}) for table in tables.values()] +
+                [('currency_plurals', {}), ('patterns', {})]
+            ))
+            if kind == 'pattern':
+                locale['patterns'][code] = row['name']
+            elif kind == 'currency' and form:
+                locale['currency_plurals'].setdefault(code, {})[form] = row['name']
+            elif kind == 'variant':
+                locale['variants'][code.lower()] = row['name']
+            else:
+                locale[tables[(kind, form)]][code.replace('_', '-')] = row['name']
+    print('skipped %d rows with codes not in the registries' % skipped)
+    return names
+
+
+def write_data(file_name, data, out_path):
+    print('writing %s/%s' % (out_path, file_name))
+    with open('%s/%s' % (out_path, file_name), 'w') as text_file:
+        json.dump(data, text_file, ensure_ascii=False, separators=(',', ':'), sort_keys=True)
+        print('', file=text_file)
+
+
+if len(sys.argv) < 2:
+    print('Error: need a path argument')
+else:
+    write_data('display_names.json', read_data(sys.argv[1]), sys.argv[1])
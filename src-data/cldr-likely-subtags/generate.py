@@ -0,0 +1,65 @@
+import csv
+import json
+import sys
+
+
+def read_registry(out_path, file_name):
+    with open('%s/%s' % (out_path, file_name)) as json_file:
+        return json.load(json_file)
+
+
+def read_data(out_path):
+    languages = read_registry(out_path, 'languages.json')
+    language_codes = set(languages.keys()) | set(
+        language['short_code'] for language in languages.values() if language['short_code']
+    )
+    script_codes = set(read_registry(out_path, 'scripts.json').keys())
+    country_codes = set(
+        country['short_code'] for country in read_registry(out_path, 'countries.json').values()
+    )
+    region_codes = set('%03d' % int(code) for code in read_registry(out_path, 'regions.json'))
+    region_codes -= set(
+        '%03d' % country['country_code']
+        for country in read_registry(out_path, 'countries.json').values()
+        if country['country_code'] is not None
+    )
+
+    def is_valid(language, script, country):
+        return (language in language_codes and script in script_codes and
+                (country in country_codes or country in region_codes))
+
+    likely = {}
+    skipped = 0
+    with open('likely_subtags.csv', newline='') as csv_file:
+        for row in csv.DictReader(csv_file):
+            from_parts = row['from'].split('_')
+            language, script, country = row['to'].split('_')
+            from_language = from_parts[0]
+            from_rest = from_parts[1:]
+            if ((from_language != 'und' and from_language not in language_codes) or
+                    any(part not in script_codes and part not in country_codes and
+                        part not in region_codes for part in from_rest) or
+                    not is_valid(language, script, country)):
+                skipped += 1
+                continue
+            likely[row['from']] = {
+                'language': language,
+                'script': script,
+                'country': country if country in country_codes else None,
+                'region': country if country in region_codes else None
+            }
+    print('skipped %d rows with codes not in the registries' % skipped)
+    return likely
+
+
+def write_data(file_name, data, out_path):
+    print('writing %s/%s' % (out_path, file_name))
+    with open('%s/%s' % (out_path, file_name), 'w') as text_file:
+        json.dump(data, text_file, ensure_ascii=False, separators=(',', ':'), sort_keys=True)
+        print('', file=text_file)
+
+
+if len(sys.argv) < 2:
+    print('Error: need a path argument')
+else:
+    write_data('likely_subtags.json', read_data(sys.argv[1]), sys.argv[1])
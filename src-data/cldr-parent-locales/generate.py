@@ -0,0 +1,24 @@
+import csv
+import json
+import sys
+
+
+def read_data():
+    parents = {}
+    with open('parent_locales.csv', newline='') as csv_file:
+        for row in csv.DictReader(csv_file):
+            parents[row['locale']] = row['parent']
+    return parents
+
+
+def write_data(file_name, data, out_path):
+    print('writing %s/%s' % (out_path, file_name))
+    with open('%s/%s' % (out_path, file_name), 'w') as text_file:
+        json.dump(data, text_file, ensure_ascii=False, separators=(',', ':'), sort_keys=True)
+        print('', file=text_file)
+
+
+if len(sys.argv) < 2:
+    print('Error: need a path argument')
+else:
+    write_data('parent_locales.json', read_data(), sys.argv[1])
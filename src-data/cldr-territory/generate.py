@@ -0,0 +1,82 @@
+import csv
+import json
+import sys
+
+STATUS_ORDER = ['official', 'de_facto_official', 'official_regional', 'official_minority', '']
+
+
+def read_registry(out_path, file_name):
+    with open('%s/%s' % (out_path, file_name)) as json_file:
+        return json.load(json_file)
+
+
+def read_data(out_path):
+    languages = read_registry(out_path, 'languages.json')
+    language_codes = set(languages.keys()) | set(
+        language['short_code'] for language in languages.values() if language['short_code']
+    )
+    country_codes = set(
+        country['short_code'] for country in read_registry(out_path, 'countries.json').values()
+    )
+
+    territories = {}
+    skipped = 0
+    with open('territories.csv', newline='') as csv_file:
+        for row in csv.DictReader(csv_file):
+            if row['alpha_2'] not in country_codes:
+                skipped += 1
+                continue
+            territories[row['alpha_2']] = {
+                'literacy_percent': float(row['literacy_percent']),
+                'languages': {}
+            }
+    print('skipped %d territories not in the country registry' % skipped)
+
+    skipped = 0
+    with open('languages.csv', newline='') as csv_file:
+        for row in csv.DictReader(csv_file):
+            # the same language in another script, such as `iu_Latn`, is
+            # merged with the language; the larger population share and the
+            # strongest official status are kept.
+            language = row['language'].split('_')[0]
+            if (row['alpha_2'] not in territories or language not in language_codes or
+                    language == 'und'):
+                skipped += 1
+                continue
+            spoken = territories[row['alpha_2']]['languages']
+            population_percent = float(row['population_percent'])
+            status = row['official_status']
+            if language in spoken:
+                population_percent = max(population_percent, spoken[language][0])
+                status = min(status, spoken[language][1], key=STATUS_ORDER.index)
+            spoken[language] = (population_percent, status)
+    print('skipped %d languages not in the registries' % skipped)
+
+    for territory in territories.values():
+        territory['languages'] = [
+            {
+                'language_code': language,
+                'population_percent': population_percent,
+                'official_status': ''.join(
+                    part.capitalize() for part in status.split('_')
+                ) if status else None
+            }
+            for language, (population_percent, status) in sorted(
+                territory['languages'].items(),
+                key=lambda language: (-language[1][0], language[0])
+            )
+        ]
+    return territories
+
+
+def write_data(file_name, data, out_path):
+    print('writing %s/%s' % (out_path, file_name))
+    with open('%s/%s' % (out_path, file_name), 'w') as text_file:
+        json.dump(data, text_file, ensure_ascii=False, separators=(',', ':'))
+        print('', file=text_file)
+
+
+if len(sys.argv) < 2:
+    print('Error: need a path argument')
+else:
+    write_data('territory_languages.json', read_data(sys.argv[1]), sys.argv[1])
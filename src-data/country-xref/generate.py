@@ -0,0 +1,56 @@
+import csv
+import json
+import sys
+
+SCHEMES = {
+    'ioc': 'Ioc',
+    'fifa': 'Fifa',
+    'itu': 'Itu',
+    'fips': 'Fips',
+    'gec': 'Gec',
+    'vehicle': 'Vehicle',
+    'marc': 'Marc'
+}
+
+
+def read_alpha_3():
+    with open('../iso-3166/all.csv', newline='') as csv_file:
+        return {row['alpha_2']: row['alpha_3'] for row in csv.DictReader(csv_file)}
+
+
+def read_data(alpha_3):
+    codes = []
+    with open('schemes.csv', newline='') as csv_file:
+        for row in csv.DictReader(csv_file):
+            for (column, scheme) in SCHEMES.items():
+                if row[column]:
+                    codes.append({
+                        'scheme': scheme,
+                        'code': row[column],
+                        'country_code': alpha_3[row['alpha_2']],
+                        'name': None,
+                        'correspondence': 'Exact'
+                    })
+    with open('partial.csv', newline='') as csv_file:
+        for row in csv.DictReader(csv_file):
+            codes.append({
+                'scheme': SCHEMES[row['scheme']],
+                'code': row['code'],
+                'country_code': alpha_3[row['alpha_2']] if row['alpha_2'] else None,
+                'name': row['name'],
+                'correspondence': 'PartOf' if row['alpha_2'] else 'NoMatch'
+            })
+    return codes
+
+
+def write_data(codes, out_path):
+    print('writing %s/country_xref.json' % out_path)
+    with open('%s/country_xref.json' % out_path, 'w') as text_file:
+        json.dump(codes, text_file, ensure_ascii=False, separators=(',', ':'))
+        print('', file=text_file)
+
+
+if len(sys.argv) < 2:
+    print('Error: need a path argument')
+else:
+    write_data(read_data(read_alpha_3()), sys.argv[1])
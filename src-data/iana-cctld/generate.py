@@ -0,0 +1,69 @@
+import json
+import re
+import sys
+
+IDN_COMMENT = re.compile(r'^// (xn--[a-z0-9-]+) .*: ([A-Z]{2})$')
+
+
+def read_icann_rules():
+    rules = []
+    in_icann = False
+    with open('public_suffix_list.dat', encoding='utf-8') as text_file:
+        for line in text_file:
+            line = line.strip()
+            if line == '// ===BEGIN ICANN DOMAINS===':
+                in_icann = True
+            elif line == '// ===END ICANN DOMAINS===':
+                break
+            elif in_icann:
+                rules.append(line)
+    return rules
+
+
+def to_punycode(label):
+    return 'xn--' + label.encode('punycode').decode('ascii')
+
+
+def read_data():
+    rules = read_icann_rules()
+    tlds = {}
+    for rule in rules:
+        if rule and not rule.startswith('//'):
+            label = rule.split('.')[-1]
+            if len(label) == 2 and label.isascii() and label not in tlds:
+                tlds[label] = {
+                    'tld': label,
+                    'unicode': None,
+                    'short_code': label.upper()
+                }
+    idn = None
+    for rule in rules:
+        if rule == '// newGTLDs':
+            break
+        match = IDN_COMMENT.match(rule)
+        if match:
+            idn = match.groups()
+        elif idn and rule and not rule.startswith('//'):
+            (a_label, code) = idn
+            if to_punycode(rule) != a_label:
+                print('Error: U-label %s does not match %s' % (rule, a_label))
+            tlds[a_label] = {
+                'tld': a_label,
+                'unicode': rule,
+                'short_code': code
+            }
+            idn = None
+    return tlds
+
+
+def write_data(file_name, data, out_path):
+    print('writing %s/%s' % (out_path, file_name))
+    with open('%s/%s' % (out_path, file_name), 'w') as text_file:
+        json.dump(data, text_file, ensure_ascii=False, separators=(',', ':'))
+        print('', file=text_file)
+
+
+if len(sys.argv) < 2:
+    print('Error: need a path argument')
+else:
+    write_data('country_tlds.json', read_data(), sys.argv[1])
@@ -0,0 +1,45 @@
+import csv
+import json
+import sys
+
+
+def parse_coordinate(text, degree_digits):
+    degrees = int(text[1:1 + degree_digits])
+    minutes = int(text[1 + degree_digits:3 + degree_digits])
+    seconds = int(text[3 + degree_digits:]) if len(text) > 3 + degree_digits else 0
+    value = degrees + minutes / 60 + seconds / 3600
+    return round(-value if text[0] == '-' else value, 6)
+
+
+def parse_coordinates(text):
+    split = max(text.rfind('+'), text.rfind('-'))
+    return (parse_coordinate(text[:split], 2), parse_coordinate(text[split:], 3))
+
+
+def read_data():
+    zones = []
+    with open('zone1970.tab', newline='', encoding='utf-8') as tab_file:
+        for row in csv.reader(tab_file, delimiter='\t'):
+            if row and not row[0].startswith('#'):
+                (latitude, longitude) = parse_coordinates(row[1])
+                zones.append({
+                    'name': row[2],
+                    'country_codes': row[0].split(','),
+                    'latitude': latitude,
+                    'longitude': longitude,
+                    'comment': row[3] if len(row) > 3 and row[3] else None
+                })
+    return zones
+
+
+def write_data(file_name, data, out_path):
+    print('writing %s/%s' % (out_path, file_name))
+    with open('%s/%s' % (out_path, file_name), 'w') as text_file:
+        json.dump(data, text_file, ensure_ascii=False, separators=(',', ':'))
+        print('', file=text_file)
+
+
+if len(sys.argv) < 2:
+    print('Error: need a path argument')
+else:
+    write_data('timezones.json', read_data(), sys.argv[1])
@@ -0,0 +1,46 @@
+import csv
+import json
+import sys
+
+
+def read_former():
+    former = {}
+    with open('former.csv', newline='') as csv_file:
+        for row in csv.DictReader(csv_file):
+            former[row['code']] = {
+                'code': row['code'],
+                'short_code': row['alpha_2'],
+                'long_code': row['alpha_3'],
+                'country_code': int(row['numeric']) if row['numeric'] else None,
+                'name': row['name'],
+                'withdrawn': int(row['withdrawn']),
+                'successors': row['successors'].split()
+            }
+    return former
+
+
+def read_reserved():
+    reserved = {}
+    with open('reserved.csv', newline='') as csv_file:
+        for row in csv.DictReader(csv_file):
+            reserved[row['alpha_2']] = {
+                'short_code': row['alpha_2'],
+                'kind': row['kind'],
+                'name': row['name'],
+                'refers_to': row['refers_to'] if row['refers_to'] else None
+            }
+    return reserved
+
+
+def write_data(file_name, data, out_path):
+    print('writing %s/%s' % (out_path, file_name))
+    with open('%s/%s' % (out_path, file_name), 'w') as text_file:
+        json.dump(data, text_file, ensure_ascii=False, separators=(',', ':'))
+        print('', file=text_file)
+
+
+if len(sys.argv) < 2:
+    print('Error: need a path argument')
+else:
+    write_data('former_countries.json', read_former(), sys.argv[1])
+    write_data('reserved_countries.json', read_reserved(), sys.argv[1])
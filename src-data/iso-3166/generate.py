@@ -3,9 +3,9 @@ import pandas as pd
 import sys
 
 def read_data():
-    data_frame = pd.read_csv('all.csv', header=0)
+    data_frame = pd.read_csv('all.csv', header=0, keep_default_na=False, na_values=[''])
 
-    regions = {}
+    regions = {1: 'World'}
 
     countries = []
 
@@ -0,0 +1,71 @@
+import json
+import sys
+import xml.etree.ElementTree as ET
+
+GEOGRAPHIC_TYPES = ['fixedLine', 'mobile']
+
+
+def compact(pattern):
+    return ''.join(pattern.split())
+
+
+def parse_lengths(text):
+    lengths = set()
+    for item in text.split(','):
+        if item.startswith('['):
+            (low, high) = item[1:-1].split('-')
+            lengths.update(range(int(low), int(high) + 1))
+        else:
+            lengths.add(int(item))
+    return lengths
+
+
+def read_data():
+    territories = ET.parse('PhoneNumberMetadata.xml').getroot().find('territories')
+    regions = {}
+    shared = {}
+    for territory in territories:
+        attributes = territory.attrib
+        if attributes['id'] == '001':
+            # non-geographic entities, such as international freephone.
+            continue
+        lengths = set()
+        patterns = []
+        for desc in territory:
+            possible = desc.find('possibleLengths')
+            if possible is not None and desc.tag != 'noInternationalDialling':
+                lengths.update(parse_lengths(possible.attrib['national']))
+            pattern = desc.find('nationalNumberPattern')
+            if desc.tag in GEOGRAPHIC_TYPES and pattern is not None:
+                patterns.append('(?:%s)' % compact(pattern.text))
+        calling_code = int(attributes['countryCode'])
+        shared.setdefault(calling_code, []).append(attributes['id'])
+        regions[attributes['id']] = {
+            'short_code': attributes['id'],
+            'calling_code': calling_code,
+            'main_country_for_code': attributes.get('mainCountryForCode') == 'true',
+            'leading_digits': attributes.get('leadingDigits'),
+            'international_prefix': attributes.get('internationalPrefix'),
+            'preferred_international_prefix': attributes.get('preferredInternationalPrefix'),
+            'national_prefix': attributes.get('nationalPrefix'),
+            'national_number_lengths': sorted(lengths),
+            'national_number_pattern': '|'.join(patterns)
+        }
+    for (calling_code, ids) in shared.items():
+        if len(ids) == 1:
+            regions[ids[0]]['main_country_for_code'] = True
+            regions[ids[0]]['national_number_pattern'] = None
+    return regions
+
+
+def write_data(regions, out_path):
+    print('writing %s/calling_codes.json' % out_path)
+    with open('%s/calling_codes.json' % out_path, 'w') as text_file:
+        json.dump(regions, text_file, separators=(',', ':'))
+        print('', file=text_file)
+
+
+if len(sys.argv) < 2:
+    print('Error: need a path argument')
+else:
+    write_data(read_data(), sys.argv[1])
@@ -0,0 +1,33 @@
+import csv
+import json
+import re
+import sys
+
+
+def read_data():
+    formats = {}
+    with open('postal.csv', newline='', encoding='utf-8') as csv_file:
+        for row in csv.DictReader(csv_file):
+            examples = row['examples'].split('|')
+            pattern = re.compile('^(?:%s)$' % row['pattern'])
+            for example in examples:
+                if not pattern.match(example):
+                    print('Error: %s example %s does not match' % (row['alpha_2'], example))
+            formats[row['alpha_2']] = {
+                'pattern': row['pattern'],
+                'examples': examples
+            }
+    return formats
+
+
+def write_data(file_name, data, out_path):
+    print('writing %s/%s' % (out_path, file_name))
+    with open('%s/%s' % (out_path, file_name), 'w') as text_file:
+        json.dump(data, text_file, ensure_ascii=False, separators=(',', ':'))
+        print('', file=text_file)
+
+
+if len(sys.argv) < 2:
+    print('Error: need a path argument')
+else:
+    write_data('postal_codes.json', read_data(), sys.argv[1])
@@ -0,0 +1,30 @@
+import csv
+import json
+import sys
+
+
+def read_data():
+    groups = {}
+    with open('development-groups.csv', newline='') as csv_file:
+        for row in csv.DictReader(csv_file):
+            status = row['developed_developing']
+            groups[row['alpha_3']] = {
+                'least_developed': row['ldc'] == 'x',
+                'land_locked_developing': row['lldc'] == 'x',
+                'small_island_developing': row['sids'] == 'x',
+                'developed': None if status == '' else status == 'Developed'
+            }
+    return groups
+
+
+def write_data(groups, out_path):
+    print('writing %s/development_groups.json' % out_path)
+    with open('%s/development_groups.json' % out_path, 'w') as text_file:
+        json.dump(groups, text_file, separators=(',', ':'))
+        print('', file=text_file)
+
+
+if len(sys.argv) < 2:
+    print('Error: need a path argument')
+else:
+    write_data(read_data(), sys.argv[1])
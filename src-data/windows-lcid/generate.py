@@ -0,0 +1,24 @@
+import csv
+import json
+import sys
+
+
+def read_data():
+    identifiers = []
+    with open('lcid.csv', newline='') as csv_file:
+        for row in csv.DictReader(csv_file):
+            identifiers.append({'lcid': int(row['lcid'], 16), 'tag': row['tag']})
+    return sorted(identifiers, key=lambda identifier: identifier['lcid'])
+
+
+def write_data(file_name, data, out_path):
+    print('writing %s/%s' % (out_path, file_name))
+    with open('%s/%s' % (out_path, file_name), 'w') as text_file:
+        json.dump(data, text_file, ensure_ascii=False, separators=(',', ':'), sort_keys=True)
+        print('', file=text_file)
+
+
+if len(sys.argv) < 2:
+    print('Error: need a path argument')
+else:
+    write_data('lcid.json', read_data(), sys.argv[1])
@@ -0,0 +1,603 @@
+/*!
+Postal address formats for each country.
+
+The layout of a postal address varies from country to country; the order of
+the fields, which fields are required for delivery, which are written in
+upper case, and even the name of the fields themselves, an administrative
+area is a "state" in the United States, a "prefecture" in Japan, and an
+"emirate" in the United Arab Emirates.
+
+The [`AddressFormatInfo`](struct.AddressFormatInfo.html) for a country
+describes this layout, and can render a [`PostalAddress`](struct.PostalAddress.html)
+into the lines of a label. Countries without a specific format use a default
+of name, organization, street address, and locality.
+
+```
+use locale_codes::address::{self, PostalAddress};
+
+let mut address = PostalAddress::new("US");
+address.name = Some("Jane Doe".to_string());
+address.street_address = vec!["1600 Amphitheatre Parkway".to_string()];
+address.locality = Some("Mountain View".to_string());
+address.administrative_area = Some("CA".to_string());
+address.postal_code = Some("94043".to_string());
+
+let lines = address::format_address(&address, Some("de")).unwrap();
+assert_eq!(
+    lines,
+    vec![
+        "Jane Doe",
+        "1600 Amphitheatre Parkway",
+        "MOUNTAIN VIEW, CA 94043",
+        "VEREINIGTE STAATEN",
+    ]
+);
+```
+
+## Source
+
+The data used here was transcribed from Google's
+[libaddressinput](https://github.com/google/libaddressinput) address metadata.
+*/
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::country;
+use crate::display_name;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// The fields of a postal address.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AddressField {
+    /// The recipient's name.
+    Name,
+    /// The recipient's organization.
+    Organization,
+    /// The street address, one or more lines.
+    StreetAddress,
+    /// A locality within the city, such as a suburb or neighborhood.
+    DependentLocality,
+    /// The city, town, or post town.
+    Locality,
+    /// The top-level subdivision of the country, such as a state.
+    AdministrativeArea,
+    /// The postal code.
+    PostalCode,
+    /// A sorting code, such as the French CEDEX.
+    SortingCode,
+}
+
+/// The local name for the administrative area of an address.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AdministrativeAreaName {
+    /// Area.
+    Area,
+    /// Canton.
+    Canton,
+    /// County.
+    County,
+    /// Department.
+    Department,
+    /// Do/Si, the Korean provinces and metropolitan cities.
+    DoSi,
+    /// Emirate.
+    Emirate,
+    /// Governorate.
+    Governorate,
+    /// Island.
+    Island,
+    /// Oblast.
+    Oblast,
+    /// Parish.
+    Parish,
+    /// Prefecture.
+    Prefecture,
+    /// Province.
+    Province,
+    /// Region.
+    Region,
+    /// State.
+    State,
+}
+
+/// The local name for the locality of an address.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LocalityName {
+    /// City.
+    City,
+    /// District.
+    District,
+    /// Post town.
+    PostTown,
+    /// Suburb.
+    Suburb,
+}
+
+/// The local name for the dependent locality of an address.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DependentLocalityName {
+    /// District.
+    District,
+    /// Neighborhood.
+    Neighborhood,
+    /// Suburb.
+    Suburb,
+    /// Townland.
+    Townland,
+    /// Village or township.
+    VillageTownship,
+}
+
+/// The local name for the postal code of an address.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PostalCodeName {
+    /// Postal code.
+    Postal,
+    /// ZIP code.
+    Zip,
+    /// PIN code.
+    Pin,
+    /// Eircode.
+    Eircode,
+}
+
+/// A representation of the postal address format for a country.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AddressFormatInfo {
+    /// The layout of the address; `%n` is a line break, `%N`, `%O`, `%A`,
+    /// `%D`, `%C`, `%S`, `%Z`, and `%X` are the fields in the order of the
+    /// `AddressField` enumeration, any other text is literal.
+    pub format: String,
+    /// The fields required for delivery.
+    pub required: Vec<AddressField>,
+    /// The fields that are written in upper case.
+    pub upper: Vec<AddressField>,
+    /// The local name for the administrative area.
+    pub administrative_area_name: AdministrativeAreaName,
+    /// The local name for the locality.
+    pub locality_name: LocalityName,
+    /// The local name for the dependent locality.
+    pub dependent_locality_name: DependentLocalityName,
+    /// The local name for the postal code.
+    pub postal_code_name: PostalCodeName,
+}
+
+/// A structured postal address.
+#[derive(Clone, Debug, Default)]
+pub struct PostalAddress {
+    /// The ISO-3166 2, or 3, character identifier of the destination country.
+    pub country_code: String,
+    /// The recipient's name.
+    pub name: Option<String>,
+    /// The recipient's organization.
+    pub organization: Option<String>,
+    /// The street address lines.
+    pub street_address: Vec<String>,
+    /// A locality within the city, such as a suburb or neighborhood.
+    pub dependent_locality: Option<String>,
+    /// The city, town, or post town.
+    pub locality: Option<String>,
+    /// The top-level subdivision of the country, such as a state.
+    pub administrative_area: Option<String>,
+    /// The postal code.
+    pub postal_code: Option<String>,
+    /// A sorting code, such as the French CEDEX.
+    pub sorting_code: Option<String>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+lazy_static! {
+    static ref FORMATS: HashMap<String, AddressFormatInfo> = load_address_formats_from_json();
+}
+
+/// Lookup the `AddressFormatInfo` for a country based on it's ISO-3166 2,
+/// or 3, character identifier; countries without a specific format return
+/// the default format. Returns `None` if the country is not known.
+pub fn lookup(country_code: &str) -> Option<&'static AddressFormatInfo> {
+    let country = country::lookup(country_code)?;
+    match FORMATS.get(&country.short_code) {
+        Some(format) => Some(format),
+        None => Some(default_format()),
+    }
+}
+
+/// Return the default address format, used for countries without a
+/// specific format.
+pub fn default_format() -> &'static AddressFormatInfo {
+    &FORMATS[DEFAULT_FORMAT]
+}
+
+/// Render an address into lines, in the format of the destination country.
+/// For international mail `sender_locale` identifies the sender's locale,
+/// and the name of the destination country in that locale is added, in upper
+/// case, as the last line, as it is read by the sender's postal service; for
+/// domestic mail it should be `None`. Returns `None` if the destination
+/// country is not known.
+pub fn format_address(address: &PostalAddress, sender_locale: Option<&str>) -> Option<Vec<String>> {
+    let mut lines = lookup(&address.country_code)?.format_lines(address);
+    if let Some(sender_locale) = sender_locale {
+        let country = country::lookup(&address.country_code)?;
+        lines.push(display_name::display_name(country, sender_locale).to_uppercase());
+    }
+    Some(lines)
+}
+
+/// Return the fields required by the destination country that are missing
+/// from the address. Returns `None` if the destination country is not known.
+pub fn missing_fields(address: &PostalAddress) -> Option<Vec<AddressField>> {
+    Some(lookup(&address.country_code)?.missing_fields(address))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl AddressField {
+    /// Return the character used for this field in an address format.
+    pub fn code(&self) -> char {
+        match self {
+            AddressField::Name => 'N',
+            AddressField::Organization => 'O',
+            AddressField::StreetAddress => 'A',
+            AddressField::DependentLocality => 'D',
+            AddressField::Locality => 'C',
+            AddressField::AdministrativeArea => 'S',
+            AddressField::PostalCode => 'Z',
+            AddressField::SortingCode => 'X',
+        }
+    }
+
+    /// Return the field for a character used in an address format.
+    pub fn from_code(code: char) -> Option<AddressField> {
+        match code {
+            'N' => Some(AddressField::Name),
+            'O' => Some(AddressField::Organization),
+            'A' => Some(AddressField::StreetAddress),
+            'D' => Some(AddressField::DependentLocality),
+            'C' => Some(AddressField::Locality),
+            'S' => Some(AddressField::AdministrativeArea),
+            'Z' => Some(AddressField::PostalCode),
+            'X' => Some(AddressField::SortingCode),
+            _ => None,
+        }
+    }
+}
+
+impl AddressFormatInfo {
+    /// Return the fields used in this format, in the order they appear.
+    pub fn fields(&self) -> Vec<AddressField> {
+        parse_format(&self.format)
+            .into_iter()
+            .flatten()
+            .filter_map(|token| match token {
+                Token::Field(field) => Some(field),
+                Token::Literal(_) => None,
+            })
+            .collect()
+    }
+
+    /// Returns `true` if the field is required for delivery.
+    pub fn is_required(&self, field: AddressField) -> bool {
+        self.required.contains(&field)
+    }
+
+    /// Return the required fields that are missing from the address.
+    pub fn missing_fields(&self, address: &PostalAddress) -> Vec<AddressField> {
+        self.required
+            .iter()
+            .filter(|field| address.value(**field).is_none())
+            .cloned()
+            .collect()
+    }
+
+    /// Render an address into lines using this format. Empty fields are
+    /// removed along with the literal text that separates them, and the
+    /// postal code is normalized where it is valid for the country.
+    pub fn format_lines(&self, address: &PostalAddress) -> Vec<String> {
+        let mut lines: Vec<String> = Vec::new();
+        for line in parse_format(&self.format) {
+            let street_only = line
+                .iter()
+                .all(|token| *token == Token::Field(AddressField::StreetAddress));
+            if street_only && !line.is_empty() {
+                lines.extend(
+                    address
+                        .street_address
+                        .iter()
+                        .filter(|street| !street.trim().is_empty())
+                        .map(|street| self.value_case(AddressField::StreetAddress, street)),
+                );
+            } else {
+                let rendered = self.format_line(line, address);
+                if !rendered.is_empty() {
+                    lines.push(rendered);
+                }
+            }
+        }
+        lines
+    }
+
+    fn format_line(&self, line: Vec<Token>, address: &PostalAddress) -> String {
+        let mut tokens: Vec<Option<Token>> = line.into_iter().map(Some).collect();
+        for index in 0..tokens.len() {
+            if let Some(Token::Field(field)) = tokens[index] {
+                if address.value(field).is_none() {
+                    tokens[index] = None;
+                    let previous = (0..index).rev().find(|i| tokens[*i].is_some());
+                    let next = (index + 1..tokens.len()).find(|i| tokens[*i].is_some());
+                    match (previous, next) {
+                        (Some(i), _) if is_literal(&tokens[i]) => tokens[i] = None,
+                        (_, Some(i)) if is_literal(&tokens[i]) => tokens[i] = None,
+                        _ => (),
+                    }
+                }
+            }
+        }
+        tokens
+            .into_iter()
+            .flatten()
+            .map(|token| match token {
+                Token::Literal(literal) => literal,
+                Token::Field(field) => self.value_case(field, &address.value(field).unwrap()),
+            })
+            .collect::<String>()
+            .trim()
+            .to_string()
+    }
+
+    fn value_case(&self, field: AddressField, value: &str) -> String {
+        if self.upper.contains(&field) {
+            value.to_uppercase()
+        } else {
+            value.to_string()
+        }
+    }
+}
+
+impl PostalAddress {
+    /// Create a new, empty, address for the destination country.
+    pub fn new(country_code: &str) -> Self {
+        PostalAddress {
+            country_code: country_code.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Return the value of a field, `None` if it is empty. Street address
+    /// lines are joined with `", "`, and the postal code is normalized if
+    /// it is valid for the destination country.
+    pub fn value(&self, field: AddressField) -> Option<String> {
+        let value = match field {
+            AddressField::Name => self.name.clone(),
+            AddressField::Organization => self.organization.clone(),
+            AddressField::StreetAddress => Some(
+                self.street_address
+                    .iter()
+                    .map(|street| street.trim())
+                    .filter(|street| !street.is_empty())
+                    .collect::<Vec<&str>>()
+                    .join(", "),
+            ),
+            AddressField::DependentLocality => self.dependent_locality.clone(),
+            AddressField::Locality => self.locality.clone(),
+            AddressField::AdministrativeArea => self.administrative_area.clone(),
+            AddressField::PostalCode => {
+                self.postal_code
+                    .as_ref()
+                    .map(|postal_code| match lookup(&self.country_code) {
+                        Some(_) => country::normalize_postal_code(&self.country_code, postal_code)
+                            .unwrap_or_else(|| postal_code.to_string()),
+                        None => postal_code.to_string(),
+                    })
+            }
+            AddressField::SortingCode => self.sorting_code.clone(),
+        };
+        value
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Literal(String),
+    Field(AddressField),
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+const DEFAULT_FORMAT: &str = "ZZ";
+
+fn is_literal(token: &Option<Token>) -> bool {
+    matches!(token, Some(Token::Literal(_)))
+}
+
+fn parse_format(format: &str) -> Vec<Vec<Token>> {
+    let mut lines: Vec<Vec<Token>> = vec![Vec::new()];
+    let mut literal = String::new();
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            literal.push(c);
+            continue;
+        }
+        let escape = chars.next();
+        let field = escape.and_then(AddressField::from_code);
+        if escape == Some('n') || field.is_some() {
+            let line = lines.last_mut().unwrap();
+            if !literal.is_empty() {
+                line.push(Token::Literal(literal.clone()));
+                literal.clear();
+            }
+            match field {
+                Some(field) => line.push(Token::Field(field)),
+                None => lines.push(Vec::new()),
+            }
+        } else {
+            literal.push(c);
+            literal.extend(escape);
+        }
+    }
+    if !literal.is_empty() {
+        lines.last_mut().unwrap().push(Token::Literal(literal));
+    }
+    lines
+}
+
+// ------------------------------------------------------------------------------------------------
+// Generated Data
+// ------------------------------------------------------------------------------------------------
+
+fn load_address_formats_from_json() -> HashMap<String, AddressFormatInfo> {
+    info!("load_address_formats_from_json - loading JSON");
+    let raw_data = include_bytes!("data/address_formats.json");
+    let format_map: HashMap<String, AddressFormatInfo> = serde_json::from_slice(raw_data).unwrap();
+    info!(
+        "load_address_formats_from_json - loaded {} formats",
+        format_map.len()
+    );
+    format_map
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn test_address_format_lookup() {
+        let japan = lookup("JPN").unwrap();
+        assert_eq!(
+            japan.administrative_area_name,
+            AdministrativeAreaName::Prefecture
+        );
+        assert!(japan.is_required(AddressField::PostalCode));
+        assert_eq!(
+            japan.fields(),
+            vec![
+                AddressField::PostalCode,
+                AddressField::AdministrativeArea,
+                AddressField::StreetAddress,
+                AddressField::Organization,
+                AddressField::Name,
+            ]
+        );
+        assert_eq!(
+            lookup("AE").unwrap().administrative_area_name,
+            AdministrativeAreaName::Emirate
+        );
+        assert_eq!(
+            lookup("IE").unwrap().postal_code_name,
+            PostalCodeName::Eircode
+        );
+        assert_eq!(lookup("AO").unwrap().format, default_format().format);
+        assert!(lookup("XX").is_none());
+    }
+
+    #[test]
+    fn test_format_address() {
+        let mut address = PostalAddress::new("GB");
+        address.organization = Some("Example Ltd".to_string());
+        address.street_address = vec!["10 Downing Street".to_string(), "  ".to_string()];
+        address.locality = Some("London".to_string());
+        address.postal_code = Some("sw1a2aa".to_string());
+        assert_eq!(
+            format_address(&address, None).unwrap(),
+            vec!["Example Ltd", "10 Downing Street", "LONDON", "SW1A 2AA"]
+        );
+
+        let mut address = PostalAddress::new("JP");
+        address.name = Some("山田太郎".to_string());
+        address.administrative_area = Some("東京都".to_string());
+        address.street_address = vec!["千代田区丸の内1-1".to_string()];
+        address.postal_code = Some("1000005".to_string());
+        assert_eq!(
+            format_address(&address, Some("en")).unwrap(),
+            vec![
+                "〒100-0005",
+                "東京都",
+                "千代田区丸の内1-1",
+                "山田太郎",
+                "JAPAN"
+            ]
+        );
+        assert_eq!(
+            format_address(&address, Some("fr-CA"))
+                .unwrap()
+                .last()
+                .unwrap(),
+            "JAPON"
+        );
+        assert_eq!(
+            format_address(&address, Some("de"))
+                .unwrap()
+                .last()
+                .unwrap(),
+            "JAPAN"
+        );
+        assert_eq!(
+            format_address(&address, Some("es"))
+                .unwrap()
+                .last()
+                .unwrap(),
+            "JAPÓN"
+        );
+    }
+
+    #[test]
+    fn test_format_address_separators() {
+        let mut address = PostalAddress::new("US");
+        address.street_address = vec!["1 Main St".to_string(), "Apt 2".to_string()];
+        address.locality = Some("Springfield".to_string());
+        address.postal_code = Some("12345".to_string());
+        assert_eq!(
+            format_address(&address, None).unwrap(),
+            vec!["1 Main St", "Apt 2", "SPRINGFIELD 12345"]
+        );
+        address.locality = None;
+        address.administrative_area = Some("il".to_string());
+        assert_eq!(
+            format_address(&address, None).unwrap(),
+            vec!["1 Main St", "Apt 2", "IL 12345"]
+        );
+
+        let mut address = PostalAddress::new("SG");
+        address.street_address = vec!["1 Raffles Place".to_string()];
+        assert_eq!(
+            format_address(&address, None).unwrap(),
+            vec!["1 Raffles Place"]
+        );
+    }
+
+    #[test]
+    fn test_missing_fields() {
+        let mut address = PostalAddress::new("USA");
+        address.street_address = vec!["1 Main St".to_string()];
+        address.locality = Some("Springfield".to_string());
+        assert_eq!(
+            missing_fields(&address).unwrap(),
+            vec![AddressField::AdministrativeArea, AddressField::PostalCode]
+        );
+        assert!(missing_fields(&PostalAddress::new("XX")).is_none());
+        assert!(missing_fields(&PostalAddress::default()).is_none());
+        assert!(format_address(&PostalAddress::new("XX"), None).is_none());
+    }
+}
@@ -0,0 +1,425 @@
+/*!
+Locale canonicalization, replacing legacy and deprecated subtags.
+
+Locale identifiers found in the wild are often not in their canonical form;
+they may use `_` as a separator, the wrong case, deprecated language codes
+such as `iw` for Hebrew, the codes of countries that no longer exist such as
+`YU` for Yugoslavia, or ISO 639-3 codes where an ISO 639-1 code exists. The
+[`canonicalize`](fn.canonicalize.html) function returns the canonical form of
+an identifier, along with the list of rules that were applied to it.
+
+```
+use locale_codes::canonical::{self, CanonicalizationRule};
+
+let canonical = canonical::canonicalize("iw_il").unwrap();
+assert_eq!(canonical.tag, "he-IL");
+assert_eq!(
+    canonical.rules,
+    vec![
+        CanonicalizationRule::Separator,
+        CanonicalizationRule::Case,
+        CanonicalizationRule::LanguageAlias,
+    ]
+);
+
+assert_eq!(canonical::canonicalize("sr-latn-rs").unwrap().tag, "sr-Latn-RS");
+assert_eq!(canonical::canonicalize("sr-YU").unwrap().tag, "sr-RS");
+assert_eq!(canonical::canonicalize("deu-Latn-DE").unwrap().tag, "de-DE");
+```
+
+Identifiers have the form `language[-script][-region][-variant]*`, where the
+language, script and region are validated against the
+[`language`](../language/index.html), [`script`](../script/index.html) and
+[`country`](../country/index.html) registries; the region may also be one of
+the UN M.49 numeric codes in the [`region`](../region/index.html) registry.
+Variants are only checked for their form.
+
+## Source - CLDR
+
+The data used here is taken from the `alias` data in the Unicode
+[CLDR](http://cldr.unicode.org/) supplemental metadata.
+*/
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use serde::Deserialize;
+
+use crate::likely_subtags::{self, UNDETERMINED};
+use crate::{country, language, region, script};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// A rule applied while canonicalizing a locale identifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CanonicalizationRule {
+    /// The `_` separator was replaced with `-`.
+    Separator,
+    /// The case of one or more subtags was normalized; lower case language
+    /// and variants, title case script, and upper case region.
+    Case,
+    /// A deprecated, or legacy, language code was replaced, for example
+    /// `iw` with `he`, or `no-bokmal` with `nb`.
+    LanguageAlias,
+    /// An ISO 639-3 language code was replaced with the ISO 639-1 code for
+    /// the same language, for example `deu` with `de`.
+    ShortLanguageCode,
+    /// A deprecated script code was replaced.
+    ScriptAlias,
+    /// A deprecated, or numeric, region code was replaced, for example
+    /// `DD` with `DE`, or `276` with `DE`.
+    RegionAlias,
+    /// The script was removed as it is the likely script for the language
+    /// and region, for example `en-Latn-US` is `en-US`.
+    RedundantScript,
+}
+
+/// A canonicalized locale identifier.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CanonicalTag {
+    /// The canonical form of the identifier.
+    pub tag: String,
+    /// The rules applied to create the canonical form, in the order they
+    /// were applied.
+    pub rules: Vec<CanonicalizationRule>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+lazy_static! {
+    static ref ALIASES: Aliases = load_aliases_from_json();
+}
+
+/// Return the canonical form of the locale identifier, along with the rules
+/// applied to create it. Returns `None` if the identifier is malformed, or
+/// if any of its subtags are not present in their registry once aliases
+/// have been replaced.
+pub fn canonicalize(tag: &str) -> Option<CanonicalTag> {
+    debug!("canonical::canonicalize: {}", tag);
+    let mut rules = Vec::new();
+    if tag.contains('_') {
+        rules.push(CanonicalizationRule::Separator);
+    }
+    let mut subtags = Subtags::parse(tag)?;
+    if subtags.to_string() != tag.replace('_', "-") {
+        rules.push(CanonicalizationRule::Case);
+    }
+    if subtags.replace_language_alias() {
+        rules.push(CanonicalizationRule::LanguageAlias);
+    }
+    if subtags.shorten_language() {
+        rules.push(CanonicalizationRule::ShortLanguageCode);
+    }
+    if subtags.replace_script_alias() {
+        rules.push(CanonicalizationRule::ScriptAlias);
+    }
+    if subtags.replace_region_alias() {
+        rules.push(CanonicalizationRule::RegionAlias);
+    }
+    if !subtags.is_valid() {
+        return None;
+    }
+    if subtags.remove_redundant_script() {
+        rules.push(CanonicalizationRule::RedundantScript);
+    }
+    Some(CanonicalTag {
+        tag: subtags.to_string(),
+        rules,
+    })
+}
+
+/// Returns `true` if the locale identifier is valid and already in its
+/// canonical form.
+pub fn is_canonical(tag: &str) -> bool {
+    canonicalize(tag).is_some_and(|canonical| canonical.rules.is_empty())
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl CanonicalTag {
+    /// Returns `true` if any rules were applied, that is the original
+    /// identifier was not in its canonical form.
+    pub fn is_changed(&self) -> bool {
+        !self.rules.is_empty()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Deserialize, Debug)]
+struct Aliases {
+    language: HashMap<String, String>,
+    script: HashMap<String, String>,
+    region: HashMap<String, Vec<String>>,
+}
+
+#[derive(Debug)]
+struct Subtags {
+    language: String,
+    script: Option<String>,
+    region: Option<String>,
+    variants: Vec<String>,
+}
+
+impl Subtags {
+    fn parse(tag: &str) -> Option<Self> {
+        let mut parts = tag.split(['-', '_']);
+        let language = parts.next()?;
+        if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic())
+        {
+            return None;
+        }
+        let mut subtags = Subtags {
+            language: language.to_ascii_lowercase(),
+            script: None,
+            region: None,
+            variants: Vec::new(),
+        };
+        for part in parts {
+            let is_alphabetic = part.chars().all(|c| c.is_ascii_alphabetic());
+            let is_numeric = part.chars().all(|c| c.is_ascii_digit());
+            if part.len() == 4
+                && is_alphabetic
+                && subtags.script.is_none()
+                && subtags.region.is_none()
+                && subtags.variants.is_empty()
+            {
+                subtags.script = Some(format!(
+                    "{}{}",
+                    part[..1].to_ascii_uppercase(),
+                    part[1..].to_ascii_lowercase()
+                ));
+            } else if ((part.len() == 2 && is_alphabetic) || (part.len() == 3 && is_numeric))
+                && subtags.region.is_none()
+                && subtags.variants.is_empty()
+            {
+                subtags.region = Some(part.to_ascii_uppercase());
+            } else if is_variant(part) {
+                subtags.variants.push(part.to_ascii_lowercase());
+            } else {
+                return None;
+            }
+        }
+        Some(subtags)
+    }
+
+    fn replace_language_alias(&mut self) -> bool {
+        let variant_key = self.variants.iter().position(|variant| {
+            ALIASES
+                .language
+                .contains_key(&format!("{}-{}", self.language, variant))
+        });
+        let replacement = if let Some(index) = variant_key {
+            let variant = self.variants.remove(index);
+            &ALIASES.language[&format!("{}-{}", self.language, variant)]
+        } else if let Some(replacement) = self.region.as_ref().and_then(|region| {
+            ALIASES
+                .language
+                .get(&format!("{}-{}", self.language, region))
+        }) {
+            self.region = None;
+            replacement
+        } else if let Some(replacement) = ALIASES.language.get(&self.language) {
+            replacement
+        } else {
+            return false;
+        };
+        let mut replacement = replacement.split('-');
+        self.language = replacement.next().unwrap().to_string();
+        for part in replacement {
+            if part.len() == 4 {
+                self.script = self.script.take().or_else(|| Some(part.to_string()));
+            } else {
+                self.region = self.region.take().or_else(|| Some(part.to_string()));
+            }
+        }
+        true
+    }
+
+    fn shorten_language(&mut self) -> bool {
+        if self.language.len() != 3 {
+            return false;
+        }
+        match language::lookup(&self.language).and_then(|info| info.short_code.as_ref()) {
+            Some(short_code) => {
+                self.language = short_code.to_string();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn replace_script_alias(&mut self) -> bool {
+        match self
+            .script
+            .as_ref()
+            .and_then(|script| ALIASES.script.get(script))
+        {
+            Some(replacement) => {
+                self.script = Some(replacement.to_string());
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn replace_region_alias(&mut self) -> bool {
+        let replacements = match self
+            .region
+            .as_ref()
+            .and_then(|region| ALIASES.region.get(region))
+        {
+            Some(replacements) => replacements,
+            None => return false,
+        };
+        let likely = likely_subtags::maximize(&self.language, self.script.as_deref(), None)
+            .and_then(|likely| likely.country);
+        self.region = match likely {
+            Some(likely) if replacements.contains(&likely) => Some(likely),
+            _ => replacements.first().cloned(),
+        };
+        true
+    }
+
+    fn remove_redundant_script(&mut self) -> bool {
+        let script = match &self.script {
+            Some(script) => script,
+            None => return false,
+        };
+        let country = self.region.as_deref().filter(|region| region.len() == 2);
+        let likely = likely_subtags::maximize(&self.language, None, country)
+            .and_then(|likely| likely.script);
+        if likely.as_ref() == Some(script) {
+            self.script = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        (self.language == UNDETERMINED || language::lookup(&self.language).is_some())
+            && self
+                .script
+                .as_ref()
+                .is_none_or(|script| script::lookup_by_alpha(script).is_some())
+            && self
+                .region
+                .as_ref()
+                .is_none_or(|region| match region.len() {
+                    2 => country::lookup(region).is_some(),
+                    _ => region
+                        .parse::<u16>()
+                        .is_ok_and(|code| region::lookup(code).is_some()),
+                })
+    }
+}
+
+impl Display for Subtags {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.language)?;
+        for subtag in self
+            .script
+            .iter()
+            .chain(self.region.iter())
+            .chain(self.variants.iter())
+        {
+            write!(f, "-{}", subtag)?;
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+pub(crate) fn is_variant(subtag: &str) -> bool {
+    subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        && match subtag.len() {
+            4 => subtag.starts_with(|c: char| c.is_ascii_digit()),
+            5..=8 => true,
+            _ => false,
+        }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Generated Data
+// ------------------------------------------------------------------------------------------------
+
+fn load_aliases_from_json() -> Aliases {
+    info!("load_aliases_from_json - loading JSON");
+    let raw_data = include_bytes!("data/aliases.json");
+    let aliases: Aliases = serde_json::from_slice(raw_data).unwrap();
+    info!(
+        "load_aliases_from_json - loaded {} language, {} script, and {} region aliases",
+        aliases.language.len(),
+        aliases.script.len(),
+        aliases.region.len()
+    );
+    aliases
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::CanonicalizationRule::*;
+    use super::*;
+
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn test_language_aliases() {
+        let tag = |tag: &str| -> String { canonicalize(tag).unwrap().tag };
+        assert_eq!(tag("iw"), "he");
+        assert_eq!(tag("in-ID"), "id-ID");
+        assert_eq!(tag("ji"), "yi");
+        assert_eq!(tag("mo"), "ro");
+        assert_eq!(tag("sh"), "sr-Latn");
+        assert_eq!(tag("sh-Cyrl-ME"), "sr-Cyrl-ME");
+        assert_eq!(tag("no_NB"), "nb");
+        assert_eq!(tag("no-bokmal"), "nb");
+        assert_eq!(tag("ger-DE"), "de-DE");
+        let canonical = canonicalize("zho-TW").unwrap();
+        assert_eq!(canonical.tag, "zh-TW");
+        assert_eq!(canonical.rules, vec![ShortLanguageCode]);
+    }
+
+    #[test]
+    fn test_region_and_script_aliases() {
+        let canonical = canonicalize("de-DD").unwrap();
+        assert_eq!(canonical.tag, "de-DE");
+        assert_eq!(canonical.rules, vec![RegionAlias]);
+        assert_eq!(canonicalize("hy-SU").unwrap().tag, "hy-AM");
+        assert_eq!(canonicalize("en-SU").unwrap().tag, "en-RU");
+        assert_eq!(canonicalize("es-484").unwrap().tag, "es-MX");
+        assert_eq!(canonicalize("es-419").unwrap().tag, "es-419");
+        assert_eq!(canonicalize("en-Qaai").unwrap().tag, "en-Zinh");
+        let canonical = canonicalize("en-latn-us").unwrap();
+        assert_eq!(canonical.tag, "en-US");
+        assert_eq!(canonical.rules, vec![Case, RedundantScript]);
+        assert_eq!(canonicalize("sr-Cyrl-ME").unwrap().tag, "sr-Cyrl-ME");
+    }
+
+    #[test]
+    fn test_canonical_tags() {
+        assert!(is_canonical("sr-Latn-RS"));
+        assert!(is_canonical("de-DE-1996"));
+        assert!(!is_canonical("sr-latn-rs"));
+        assert!(!canonicalize("de_de").unwrap().rules.is_empty());
+        assert!(canonicalize("xx-YY").is_none());
+        assert!(canonicalize("en-ZZ").is_none());
+        assert!(canonicalize("en-US-x").is_none());
+        assert!(canonicalize("").is_none());
+    }
+}
@@ -47,20 +47,42 @@ pub struct CodesetInfo {
 
 lazy_static! {
     static ref CODESETS: HashMap<String, CodesetInfo> = load_code_sets_from_json();
+    static ref ALIAS_LOOKUP: HashMap<String, String> = make_alias_lookup();
 }
 
 /// Lookup a `CodesetInfo` based on it's name, returning `None` if the name
 /// does not exist in the current IANA data set.
 pub fn lookup(name: &str) -> Option<&'static CodesetInfo> {
-    assert!(name.len() > 0, "codeset name may not be empty");
+    assert!(!name.is_empty(), "codeset name may not be empty");
     CODESETS.get(name)
 }
 
+/// Lookup a `CodesetInfo` based on either it's name, or any of the names it
+/// is also known as; case and punctuation are ignored so that `utf8` and
+/// `ISO8859-1`, as commonly used in POSIX locale names, are found.
+pub fn lookup_by_alias(name: &str) -> Option<&'static CodesetInfo> {
+    match ALIAS_LOOKUP.get(&normalize_name(name)) {
+        Some(name) => CODESETS.get(name),
+        None => None,
+    }
+}
+
 /// Return all the registered script names.
 pub fn all_names() -> Vec<String> {
     CODESETS.keys().cloned().collect()
 }
 
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
 // ------------------------------------------------------------------------------------------------
 // Generated Data
 // ------------------------------------------------------------------------------------------------
@@ -76,6 +98,24 @@ fn load_code_sets_from_json() -> HashMap<String, CodesetInfo> {
     code_set_map
 }
 
+fn make_alias_lookup() -> HashMap<String, String> {
+    info!("make_alias_lookup - create from CODESETS");
+    let mut lookup_map: HashMap<String, String> = HashMap::new();
+    for codeset in CODESETS.values() {
+        for alias in &codeset.also_known_as {
+            lookup_map
+                .entry(normalize_name(alias))
+                .or_insert_with(|| codeset.name.to_string());
+        }
+    }
+    // a codeset's own name takes precedence over another's alias.
+    for codeset in CODESETS.values() {
+        lookup_map.insert(normalize_name(&codeset.name), codeset.name.to_string());
+    }
+    info!("make_alias_lookup - mapped {} names", lookup_map.len());
+    lookup_map
+}
+
 // ------------------------------------------------------------------------------------------------
 // Unit Tests
 // ------------------------------------------------------------------------------------------------
@@ -93,9 +133,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_codeset_alias() {
+        assert_eq!(lookup_by_alias("utf8").unwrap().name, "UTF-8");
+        assert_eq!(lookup_by_alias("ISO8859-1").unwrap().mib_code, 4);
+        assert_eq!(lookup_by_alias("latin1").unwrap().mib_code, 4);
+        assert_eq!(lookup_by_alias("eucJP").unwrap().mib_code, 18);
+        assert!(lookup_by_alias("utf99").is_none());
+    }
+
     #[test]
     fn test_bad_codeset_code() {
-        match lookup(&"UTF-99") {
+        match lookup("UTF-99") {
             None => (),
             Some(_) => panic!("was expecting a None in response"),
         }
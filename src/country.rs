@@ -8,16 +8,57 @@ and subdivisions. However, it does not define the names of countries
 Bulletin Country Names and the Country and Region Codes for Statistical
 Use maintained by the United Nations Statistics Divisions).
 
+Codes that have been removed from ISO 3166-1 are recorded in ISO 3166-3,
+_Code for formerly used names of countries_, and are available from the
+[`lookup_former`](fn.lookup_former.html) function. ISO 3166-1 also reserves
+codes that are not assigned to any country, for example `UK` and `EU`; the
+[`lookup_with_status`](fn.lookup_with_status.html) function will resolve
+any of these.
+
+Country code top-level domains (ccTLDs) are mostly, but not entirely, the
+lower-case 2-character code; the [`country_for_tld`](fn.country_for_tld.html)
+and [`tlds_for_country`](fn.tlds_for_country.html) functions map between them,
+including internationalized (IDN) ccTLDs such as `.рф`. Similarly the
+[`flag_emoji`](fn.flag_emoji.html) and [`country_from_flag`](fn.country_from_flag.html)
+functions map to and from the Unicode regional indicator flag sequences.
+
+For countries that use postal codes the
+[`postal_code_format`](fn.postal_code_format.html) function returns the
+expected format, and examples; the
+[`normalize_postal_code`](fn.normalize_postal_code.html) function will
+correct the case and spacing of user input.
+
+```
+use locale_codes::country;
+
+assert_eq!(
+    country::normalize_postal_code("GB", "sw1a1aa"),
+    Some("SW1A 1AA".to_string())
+);
+assert!(!country::uses_postal_code("HK"));
+```
+
 ## Source - ISO 3166
 
 The data used here is taken from the page
 [Github](https://github.com/lukes/ISO-3166-Countries-with-Regional-Codes).
+The formerly used and reserved codes were transcribed from the ISO
+[Online Browsing Platform](https://www.iso.org/obp/ui/#search).
+The ccTLDs are taken from the ICANN section of the
+[Public Suffix List](https://publicsuffix.org/), and the postal code formats
+were transcribed from Google's
+[libaddressinput](https://github.com/google/libaddressinput) metadata.
 */
 
 use std::collections::HashMap;
 
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+use crate::address::{self, AddressFormatInfo};
+use crate::telephone::{self, CallingCodeInfo};
+use crate::CodeStatus;
+
 // ------------------------------------------------------------------------------------------------
 // Public Types
 // ------------------------------------------------------------------------------------------------
@@ -43,6 +84,128 @@ pub struct CountryInfo {
     pub intermediate_region_code: Option<u16>,
 }
 
+/// A grouping of countries, by development status, identified in the UN M.49
+/// standard.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DevelopmentGroup {
+    /// Least Developed Countries (LDC).
+    LeastDeveloped,
+    /// Land Locked Developing Countries (LLDC).
+    LandLockedDeveloping,
+    /// Small Island Developing States (SIDS).
+    SmallIslandDeveloping,
+    /// Countries in the developed regions.
+    Developed,
+    /// Countries in the developing regions.
+    Developing,
+}
+
+/// The UN M.49 development groupings for a country.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DevelopmentStatus {
+    /// Is this one of the Least Developed Countries (LDC).
+    pub least_developed: bool,
+    /// Is this one of the Land Locked Developing Countries (LLDC).
+    pub land_locked_developing: bool,
+    /// Is this one of the Small Island Developing States (SIDS).
+    pub small_island_developing: bool,
+    /// Is this country in the developed, or developing, regions; `None` for
+    /// areas, such as Antarctica, that are in neither.
+    pub developed: Option<bool>,
+}
+
+/// A representation of a formerly used country code, as maintained in
+/// ISO 3166-3.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FormerCountryInfo {
+    /// The ISO-3166, part 3, 4-character identifier of the former country.
+    /// This is the primary identifier.
+    pub code: String,
+    /// The withdrawn ISO-3166, part 1, 2-character identifier.
+    pub short_code: String,
+    /// The withdrawn ISO-3166, part 1, 3-character identifier.
+    pub long_code: String,
+    /// The withdrawn numeric identifier, if one was assigned.
+    pub country_code: Option<u16>,
+    /// The name of the former country, in English.
+    pub name: String,
+    /// The year in which the code was withdrawn.
+    pub withdrawn: u16,
+    /// The 2-character identifiers of the successor countries, these may
+    /// themselves be formerly used codes.
+    pub successors: Vec<String>,
+}
+
+/// The kind of reservation made by ISO 3166-1 for a code that is not
+/// assigned to a country.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReservationKind {
+    /// Reserved at the request of a national body, or international
+    /// organization, for a particular use.
+    Exceptional,
+    /// Reserved for a period after the code was withdrawn from use.
+    Transitional,
+    /// Used in other coding systems, such as vehicle registration, and so
+    /// reserved to avoid conflict.
+    Indeterminate,
+    /// Used by intellectual property organizations, and agreed not to be
+    /// used for countries.
+    NotUsed,
+}
+
+/// A representation of a reserved ISO 3166-1 code.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ReservedCountryInfo {
+    /// The reserved ISO-3166, part 1, 2-character identifier.
+    pub short_code: String,
+    /// The kind of reservation.
+    pub kind: ReservationKind,
+    /// The name of the entity for which the code is reserved, in English.
+    pub name: String,
+    /// The 2-character identifier of the assigned country this code refers
+    /// to, if any.
+    pub refers_to: Option<String>,
+}
+
+/// The result of [`lookup_with_status`](fn.lookup_with_status.html), the
+/// entry that best describes a code.
+#[derive(Clone, Copy, Debug)]
+pub enum CountryEntry {
+    /// A currently assigned country.
+    Assigned(&'static CountryInfo),
+    /// A formerly used country code.
+    Former(&'static FormerCountryInfo),
+    /// A reserved code, not associated with any former country.
+    Reserved(&'static ReservedCountryInfo),
+}
+
+/// A representation of a country code top-level domain (ccTLD) delegated in
+/// the DNS root zone.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CountryTldInfo {
+    /// The ASCII form of the domain, without the leading `.`; for an
+    /// internationalized domain this is the punycode A-label, such as
+    /// `xn--p1ai`. This is the primary identifier.
+    pub tld: String,
+    /// The Unicode U-label of an internationalized domain, such as `рф`.
+    pub unicode: Option<String>,
+    /// The ISO-3166, part 1, 2-character identifier the domain was
+    /// delegated for; this may be a reserved code, such as `UK` or `EU`, or
+    /// a formerly used one, such as `SU`.
+    pub short_code: String,
+}
+
+/// The format of the postal codes used within a country.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PostalCodeInfo {
+    /// A regular expression matching the canonical form of a postal code,
+    /// including any space or hyphen separator.
+    pub pattern: String,
+    /// Example postal codes, in canonical form, suitable for use as a UI
+    /// placeholder.
+    pub examples: Vec<String>,
+}
+
 // ------------------------------------------------------------------------------------------------
 // Public Functions
 // ------------------------------------------------------------------------------------------------
@@ -50,16 +213,24 @@ pub struct CountryInfo {
 lazy_static! {
     static ref COUNTRIES: HashMap<String, CountryInfo> = load_countries_from_json();
     static ref LOOKUP: HashMap<String, String> = make_country_lookup();
+    static ref NUMERIC_LOOKUP: HashMap<u16, String> = make_country_numeric_lookup();
+    static ref FORMER: HashMap<String, FormerCountryInfo> = load_former_countries_from_json();
+    static ref FORMER_LOOKUP: HashMap<String, String> = make_former_country_lookup();
+    static ref RESERVED: HashMap<String, ReservedCountryInfo> = load_reserved_countries_from_json();
+    static ref TLDS: HashMap<String, CountryTldInfo> = load_country_tlds_from_json();
+    static ref TLD_LOOKUP: HashMap<String, String> = make_tld_lookup();
+    static ref TLD_COUNTRY_LOOKUP: HashMap<String, Vec<String>> = make_tld_country_lookup();
+    static ref POSTAL: HashMap<String, PostalCodeInfo> = load_postal_codes_from_json();
+    static ref POSTAL_PATTERNS: HashMap<String, Regex> = make_postal_patterns();
+    static ref DEVELOPMENT: HashMap<String, DevelopmentStatus> =
+        load_development_groups_from_json();
 }
 
-/// Lookup a `CountryInfo` based on it's ISO-3166 identifier, returning
-/// `None` if the name does not exist in the current ISO data set.
+/// Lookup a `CountryInfo` based on it's ISO-3166 2, or 3, character
+/// identifier, returning `None` if the name does not exist in the current
+/// ISO data set, or is not 2, or 3, characters long.
 pub fn lookup(code: &str) -> Option<&'static CountryInfo> {
     debug!("lookup_country: {}", code);
-    assert!(
-        code.len() == 2 || code.len() == 3,
-        "country code must be either 2, or 3, characters long."
-    );
     match code.len() {
         3 => {
             debug!("lookup_country: 3-character code");
@@ -79,11 +250,503 @@ pub fn lookup(code: &str) -> Option<&'static CountryInfo> {
     }
 }
 
+/// Lookup a `CountryInfo` based on it's ISO-3166 numeric identifier, returning
+/// `None` if the code does not exist in the current ISO data set.
+pub fn lookup_by_numeric(numeric_code: u16) -> Option<&'static CountryInfo> {
+    debug!("lookup_by_numeric: {}", numeric_code);
+    match NUMERIC_LOOKUP.get(&numeric_code) {
+        Some(v) => lookup(v),
+        None => None,
+    }
+}
+
+/// Lookup a `FormerCountryInfo` based on it's ISO-3166, part 3, 4-character
+/// identifier, or it's withdrawn 2, or 3, character identifier. Some
+/// withdrawn codes have been used more than once, in which case the most
+/// recently withdrawn is returned.
+pub fn lookup_former(code: &str) -> Option<&'static FormerCountryInfo> {
+    debug!("lookup_former: {}", code);
+    match code.len() {
+        4 => FORMER.get(code),
+        _ => match FORMER_LOOKUP.get(code) {
+            Some(v) => FORMER.get(v),
+            None => None,
+        },
+    }
+}
+
+/// Lookup a `ReservedCountryInfo` based on it's reserved ISO-3166, part 1,
+/// 2-character identifier.
+pub fn lookup_reserved(code: &str) -> Option<&'static ReservedCountryInfo> {
+    RESERVED.get(code)
+}
+
+/// Lookup any code, assigned, formerly used, or reserved, returning it's
+/// status as well as the entry that best describes it. Codes that are both
+/// reserved and formerly used, such as `YU`, are returned as a
+/// `CountryEntry::Former` as this carries more information.
+///
+/// ```
+/// use locale_codes::country::{self, CountryEntry};
+/// use locale_codes::CodeStatus;
+///
+/// let (status, entry) = country::lookup_with_status("UK");
+/// assert_eq!(status, CodeStatus::Reserved);
+/// assert_eq!(entry.unwrap().country().unwrap().code, "GBR");
+/// ```
+pub fn lookup_with_status(code: &str) -> (CodeStatus, Option<CountryEntry>) {
+    let status = code_status(code);
+    let entry = match status {
+        CodeStatus::Assigned => lookup(code).map(CountryEntry::Assigned),
+        CodeStatus::PrivateUse => None,
+        _ => match lookup_former(code) {
+            Some(former) => Some(CountryEntry::Former(former)),
+            None => lookup_reserved(code).map(CountryEntry::Reserved),
+        },
+    };
+    (status, entry)
+}
+
+/// Classify an ISO-3166 2, 3, or 4, character identifier. The user-assigned
+/// ranges, `AA`, `QM` to `QZ`, `XA` to `XZ`, and `ZZ` (and the corresponding
+/// 3-character ranges) are reported as `CodeStatus::PrivateUse`; note that
+/// this includes the widely used `XK` for Kosovo. Formerly used codes are
+/// reported as `CodeStatus::Reserved` while ISO still reserves them, and
+/// `CodeStatus::Withdrawn` after.
+pub fn code_status(code: &str) -> CodeStatus {
+    let bytes = code.as_bytes();
+    if !(bytes.len() == 2 || bytes.len() == 3 || bytes.len() == 4)
+        || !bytes.iter().all(u8::is_ascii_uppercase)
+    {
+        CodeStatus::Unassigned
+    } else if bytes.len() == 4 {
+        if FORMER.contains_key(code) {
+            CodeStatus::Withdrawn
+        } else {
+            CodeStatus::Unassigned
+        }
+    } else if lookup(code).is_some() {
+        CodeStatus::Assigned
+    } else if is_user_assigned(bytes[0], bytes[1]) {
+        CodeStatus::PrivateUse
+    } else if RESERVED.contains_key(code)
+        || lookup_former(code).is_some_and(|former| RESERVED.contains_key(&former.short_code))
+    {
+        CodeStatus::Reserved
+    } else if FORMER_LOOKUP.contains_key(code) {
+        CodeStatus::Withdrawn
+    } else {
+        CodeStatus::Unassigned
+    }
+}
+
 /// Return all the registered ISO-3166 2-character country codes.
 pub fn all_codes() -> Vec<String> {
     COUNTRIES.keys().cloned().collect()
 }
 
+/// Return all the countries that belong to the given UN M.49 development
+/// group, in code order.
+///
+/// ```
+/// use locale_codes::country::{self, DevelopmentGroup};
+///
+/// let lldc = country::countries_in_group(DevelopmentGroup::LandLockedDeveloping);
+/// assert!(lldc.iter().any(|c| c.code == "BOL"));
+/// ```
+pub fn countries_in_group(group: DevelopmentGroup) -> Vec<&'static CountryInfo> {
+    let mut countries: Vec<&'static CountryInfo> = COUNTRIES
+        .values()
+        .filter(|country| country.is_in_group(group))
+        .collect();
+    countries.sort_by(|lhs, rhs| lhs.code.cmp(&rhs.code));
+    countries
+}
+
+/// Return an iterator over all the registered countries, in no particular
+/// order.
+pub(crate) fn all_countries() -> impl Iterator<Item = &'static CountryInfo> {
+    COUNTRIES.values()
+}
+
+/// Return all the ISO-3166, part 3, 4-character former country codes.
+pub fn all_former_codes() -> Vec<String> {
+    FORMER.keys().cloned().collect()
+}
+
+/// Return all the reserved ISO-3166, part 1, 2-character codes.
+pub fn all_reserved_codes() -> Vec<String> {
+    RESERVED.keys().cloned().collect()
+}
+
+/// Lookup a `CountryTldInfo` based on the domain name, with or without the
+/// leading `.`, in any case. Internationalized domains may be given either
+/// as the punycode A-label or the Unicode U-label.
+pub fn lookup_tld(tld: &str) -> Option<&'static CountryTldInfo> {
+    debug!("lookup_tld: {}", tld);
+    let tld = tld.trim_start_matches('.').to_lowercase();
+    match TLDS.get(&tld) {
+        Some(v) => Some(v),
+        None => match TLD_LOOKUP.get(&tld) {
+            Some(v) => TLDS.get(v),
+            None => None,
+        },
+    }
+}
+
+/// Lookup the `CountryInfo` for a ccTLD. Domains delegated for reserved
+/// codes resolve to the country the code refers to, so `.uk` returns the
+/// United Kingdom (`GB`) and `.ac` returns Saint Helena, Ascension and
+/// Tristan da Cunha (`SH`); `.eu` and `.su` have no corresponding country.
+///
+/// ```
+/// use locale_codes::country;
+///
+/// assert_eq!(country::country_for_tld(".uk").unwrap().code, "GBR");
+/// assert_eq!(country::country_for_tld("рф").unwrap().code, "RUS");
+/// assert!(country::country_for_tld("eu").is_none());
+/// ```
+pub fn country_for_tld(tld: &str) -> Option<&'static CountryInfo> {
+    match lookup_tld(tld) {
+        Some(tld) => tld.country(),
+        None => None,
+    }
+}
+
+/// Return all the ccTLDs that resolve to the country identified by it's
+/// ISO-3166 2, or 3, character identifier; ASCII domains first, then
+/// internationalized ones, each in order.
+pub fn tlds_for_country(code: &str) -> Vec<&'static CountryTldInfo> {
+    let country = match lookup(code) {
+        Some(country) => country,
+        None => return Vec::new(),
+    };
+    match TLD_COUNTRY_LOOKUP.get(&country.code) {
+        Some(tlds) => tlds.iter().filter_map(|tld| TLDS.get(tld)).collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Return all the delegated ccTLDs, in their ASCII form.
+pub fn all_tlds() -> Vec<String> {
+    TLDS.keys().cloned().collect()
+}
+
+/// Return the Unicode flag emoji for a country identified by it's ISO-3166
+/// 2, or 3, character identifier, or for one of the subdivisions `GB-ENG`,
+/// `GB-SCT`, and `GB-WLS`. Country flags are a pair of regional indicator
+/// symbols, subdivision flags are a tag sequence. The reserved, and
+/// user-assigned, codes that Unicode recommends a flag for, such as `EU`,
+/// `UN` and `XK` for Kosovo, are also supported; `None` is returned for any
+/// other code.
+///
+/// ```
+/// use locale_codes::country;
+///
+/// assert_eq!(country::flag_emoji("CH"), Some("🇨🇭".to_string()));
+/// assert_eq!(country::flag_emoji("EU"), Some("🇪🇺".to_string()));
+/// assert_eq!(country::flag_emoji("XK"), Some("🇽🇰".to_string()));
+/// assert!(country::flag_emoji("XA").is_none());
+/// ```
+pub fn flag_emoji(code: &str) -> Option<String> {
+    if FLAG_SUBDIVISIONS.contains(&code) {
+        let mut flag = String::from(BLACK_FLAG);
+        flag.extend(
+            code.chars()
+                .filter(|c| *c != '-')
+                .map(|c| char::from_u32(TAG_BASE + c.to_ascii_lowercase() as u32).unwrap()),
+        );
+        flag.push(CANCEL_TAG);
+        return Some(flag);
+    }
+    let short_code = match code.len() {
+        2 if FLAG_RESERVED.contains(&code) => code,
+        2 | 3 => &lookup(code)?.short_code,
+        _ => return None,
+    };
+    Some(
+        short_code
+            .chars()
+            .map(|c| char::from_u32(REGIONAL_INDICATOR_A + (c as u32 - 'A' as u32)).unwrap())
+            .collect(),
+    )
+}
+
+/// Decode a flag emoji, returning the code it represents; either an
+/// ISO-3166 2-character identifier, or a subdivision code such as `GB-SCT`.
+/// Returns `None` if the string is not a single flag for a supported code.
+pub fn flag_code(flag: &str) -> Option<String> {
+    let chars: Vec<char> = flag.chars().collect();
+    let code: String = if chars.len() == 2 {
+        chars
+            .iter()
+            .map(|c| from_offset(*c, REGIONAL_INDICATOR_A, 'A'))
+            .collect::<Option<String>>()?
+    } else if chars.len() > 3 && chars[0] == BLACK_FLAG && chars[chars.len() - 1] == CANCEL_TAG {
+        let tags = chars[1..chars.len() - 1]
+            .iter()
+            .map(|c| from_offset(*c, TAG_BASE + 'a' as u32, 'A'))
+            .collect::<Option<String>>()?;
+        if tags.len() < 3 {
+            return None;
+        }
+        format!("{}-{}", &tags[..2], &tags[2..])
+    } else {
+        return None;
+    };
+    if flag_emoji(&code).is_some() {
+        Some(code)
+    } else {
+        None
+    }
+}
+
+/// Decode a flag emoji, returning the country it represents. Subdivision
+/// flags return their country, and the reserved codes resolve to the
+/// country they refer to, if any.
+///
+/// ```
+/// use locale_codes::country;
+///
+/// assert_eq!(country::country_from_flag("🇨🇭").unwrap().code, "CHE");
+/// assert!(country::country_from_flag("🇪🇺").is_none());
+/// ```
+pub fn country_from_flag(flag: &str) -> Option<&'static CountryInfo> {
+    let code = flag_code(flag)?;
+    match lookup_with_status(&code[..2]) {
+        (_, Some(entry)) => entry.country(),
+        _ => None,
+    }
+}
+
+/// Return the postal code format for the country identified by it's
+/// ISO-3166 2, or 3, character identifier, returning `None` if the country
+/// does not use postal codes.
+pub fn postal_code_format(code: &str) -> Option<&'static PostalCodeInfo> {
+    POSTAL.get(&lookup(code)?.short_code)
+}
+
+/// Returns `true` if the identified country uses postal codes.
+pub fn uses_postal_code(code: &str) -> bool {
+    postal_code_format(code).is_some()
+}
+
+/// Returns `true` if `postal_code` is valid for the identified country,
+/// ignoring the case, spacing, and hyphenation of the input. Always `false`
+/// for countries that do not use postal codes.
+pub fn validate_postal_code(code: &str, postal_code: &str) -> bool {
+    normalize_postal_code(code, postal_code).is_some()
+}
+
+/// Return the canonical form of `postal_code` for the identified country,
+/// or `None` if it is not valid for the country.
+pub fn normalize_postal_code(code: &str, postal_code: &str) -> Option<String> {
+    postal_code_format(code)?.normalize(postal_code)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl CountryInfo {
+    /// Return the postal code format for this country, `None` if the
+    /// country does not use postal codes.
+    pub fn postal_code_format(&self) -> Option<&'static PostalCodeInfo> {
+        POSTAL.get(&self.short_code)
+    }
+
+    /// Return the postal address format for this country; countries without
+    /// a specific format use the default format.
+    pub fn address_format(&self) -> &'static AddressFormatInfo {
+        address::lookup(&self.short_code).unwrap()
+    }
+
+    /// Return the Unicode flag emoji for this country.
+    pub fn flag_emoji(&self) -> String {
+        flag_emoji(&self.short_code).unwrap()
+    }
+
+    /// Return the E.164 calling code details for this country, `None` if
+    /// the country has no calling code.
+    pub fn calling_code(&self) -> Option<&'static CallingCodeInfo> {
+        telephone::lookup(&self.short_code)
+    }
+
+    /// Return the UN M.49 development groupings for this country, if known.
+    pub fn development_status(&self) -> Option<&'static DevelopmentStatus> {
+        DEVELOPMENT.get(&self.code)
+    }
+
+    /// Returns `true` if this country belongs to the given UN M.49
+    /// development group.
+    pub fn is_in_group(&self, group: DevelopmentGroup) -> bool {
+        match self.development_status() {
+            Some(status) => status.is_in_group(group),
+            None => false,
+        }
+    }
+}
+
+impl DevelopmentGroup {
+    /// Return the UN M.49 numeric code for this group, if one is assigned.
+    pub fn code(&self) -> Option<u16> {
+        match self {
+            DevelopmentGroup::LeastDeveloped => Some(199),
+            DevelopmentGroup::LandLockedDeveloping => Some(432),
+            DevelopmentGroup::SmallIslandDeveloping => Some(722),
+            _ => None,
+        }
+    }
+}
+
+impl DevelopmentStatus {
+    /// Returns `true` if this status includes the given development group.
+    pub fn is_in_group(&self, group: DevelopmentGroup) -> bool {
+        match group {
+            DevelopmentGroup::LeastDeveloped => self.least_developed,
+            DevelopmentGroup::LandLockedDeveloping => self.land_locked_developing,
+            DevelopmentGroup::SmallIslandDeveloping => self.small_island_developing,
+            DevelopmentGroup::Developed => self.developed == Some(true),
+            DevelopmentGroup::Developing => self.developed == Some(false),
+        }
+    }
+}
+
+impl FormerCountryInfo {
+    /// Return the currently assigned countries that succeeded this one,
+    /// following any successors that are themselves formerly used codes.
+    pub fn successor_countries(&self) -> Vec<&'static CountryInfo> {
+        let mut countries: Vec<&'static CountryInfo> = Vec::new();
+        for successor in &self.successors {
+            match lookup(successor) {
+                Some(country) => countries.push(country),
+                None => {
+                    if let Some(former) = lookup_former(successor) {
+                        if former.withdrawn > self.withdrawn {
+                            countries.extend(former.successor_countries());
+                        }
+                    }
+                }
+            }
+        }
+        countries
+    }
+}
+
+impl ReservedCountryInfo {
+    /// Return the assigned country this reserved code refers to, if any.
+    pub fn country(&self) -> Option<&'static CountryInfo> {
+        match &self.refers_to {
+            Some(code) => lookup(code),
+            None => None,
+        }
+    }
+}
+
+impl CountryEntry {
+    /// Resolve this entry to a single, currently assigned, country. For a
+    /// former country this is only possible where there is a single
+    /// successor.
+    pub fn country(&self) -> Option<&'static CountryInfo> {
+        match self {
+            CountryEntry::Assigned(country) => Some(country),
+            CountryEntry::Former(former) => {
+                let successors = former.successor_countries();
+                if successors.len() == 1 {
+                    Some(successors[0])
+                } else {
+                    None
+                }
+            }
+            CountryEntry::Reserved(reserved) => reserved.country(),
+        }
+    }
+}
+
+impl PostalCodeInfo {
+    /// Returns `true` if `postal_code` is exactly in the canonical form.
+    pub fn is_match(&self, postal_code: &str) -> bool {
+        POSTAL_PATTERNS[&self.pattern].is_match(postal_code)
+    }
+
+    /// Return the canonical form of `postal_code`; the input is upper-cased
+    /// and any spaces or hyphens are removed, then, if this does not match,
+    /// a single separator is inserted where the format requires one.
+    pub fn normalize(&self, postal_code: &str) -> Option<String> {
+        let compact: String = postal_code
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '-')
+            .flat_map(char::to_uppercase)
+            .collect();
+        if compact.is_empty() {
+            None
+        } else if self.is_match(&compact) {
+            Some(compact)
+        } else {
+            for (index, _) in compact.char_indices().skip(1) {
+                for separator in &[' ', '-'] {
+                    let candidate =
+                        format!("{}{}{}", &compact[..index], separator, &compact[index..]);
+                    if self.is_match(&candidate) {
+                        return Some(candidate);
+                    }
+                }
+            }
+            None
+        }
+    }
+}
+
+impl CountryTldInfo {
+    /// Returns `true` if this is an internationalized domain name.
+    pub fn is_idn(&self) -> bool {
+        self.unicode.is_some()
+    }
+
+    /// Return the currently assigned country this domain was delegated for,
+    /// resolving reserved and formerly used codes where possible.
+    pub fn country(&self) -> Option<&'static CountryInfo> {
+        match lookup_with_status(&self.short_code) {
+            (_, Some(entry)) => entry.country(),
+            _ => None,
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+const REGIONAL_INDICATOR_A: u32 = 0x1F1E6;
+const TAG_BASE: u32 = 0xE0000;
+const BLACK_FLAG: char = '\u{1F3F4}';
+const CANCEL_TAG: char = '\u{E007F}';
+
+/// Reserved, and user-assigned, codes with a flag in the Unicode recommended
+/// (RGI) emoji set.
+const FLAG_RESERVED: [&str; 9] = ["AC", "CP", "DG", "EA", "EU", "IC", "TA", "UN", "XK"];
+
+/// Subdivisions with a flag in the Unicode recommended (RGI) emoji set.
+const FLAG_SUBDIVISIONS: [&str; 3] = ["GB-ENG", "GB-SCT", "GB-WLS"];
+
+fn from_offset(c: char, base: u32, first: char) -> Option<char> {
+    let offset = (c as u32).checked_sub(base)?;
+    if offset < 26 {
+        char::from_u32(first as u32 + offset)
+    } else {
+        None
+    }
+}
+
+fn is_user_assigned(first: u8, second: u8) -> bool {
+    match first {
+        b'A' | b'Z' => second == first,
+        b'Q' => second >= b'M',
+        b'X' => true,
+        _ => false,
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 // Generated Data
 // ------------------------------------------------------------------------------------------------
@@ -112,6 +775,136 @@ fn make_country_lookup() -> HashMap<String, String> {
     lookup_map
 }
 
+fn make_country_numeric_lookup() -> HashMap<u16, String> {
+    info!("make_country_numeric_lookup - create from COUNTRIES");
+    let lookup_map: HashMap<u16, String> = COUNTRIES
+        .values()
+        .map(|country| (country.country_code, country.code.to_string()))
+        .collect();
+    info!(
+        "make_country_numeric_lookup - mapped {} countries",
+        lookup_map.len()
+    );
+    lookup_map
+}
+
+fn load_development_groups_from_json() -> HashMap<String, DevelopmentStatus> {
+    info!("load_development_groups_from_json - loading JSON");
+    let raw_data = include_bytes!("data/development_groups.json");
+    let group_map: HashMap<String, DevelopmentStatus> = serde_json::from_slice(raw_data).unwrap();
+    info!(
+        "load_development_groups_from_json - loaded {} countries",
+        group_map.len()
+    );
+    group_map
+}
+
+fn load_former_countries_from_json() -> HashMap<String, FormerCountryInfo> {
+    info!("load_former_countries_from_json - loading JSON");
+    let raw_data = include_bytes!("data/former_countries.json");
+    let former_map: HashMap<String, FormerCountryInfo> = serde_json::from_slice(raw_data).unwrap();
+    info!(
+        "load_former_countries_from_json - loaded {} former countries",
+        former_map.len()
+    );
+    former_map
+}
+
+fn make_former_country_lookup() -> HashMap<String, String> {
+    info!("make_former_country_lookup - create from FORMER");
+    let mut ordered: Vec<&FormerCountryInfo> = FORMER.values().collect();
+    ordered.sort_by_key(|former| former.withdrawn);
+    let mut lookup_map: HashMap<String, String> = HashMap::new();
+    for former in ordered {
+        lookup_map.insert(former.short_code.to_string(), former.code.to_string());
+        lookup_map.insert(former.long_code.to_string(), former.code.to_string());
+    }
+    info!(
+        "make_former_country_lookup - mapped {} former countries",
+        lookup_map.len()
+    );
+    lookup_map
+}
+
+fn load_reserved_countries_from_json() -> HashMap<String, ReservedCountryInfo> {
+    info!("load_reserved_countries_from_json - loading JSON");
+    let raw_data = include_bytes!("data/reserved_countries.json");
+    let reserved_map: HashMap<String, ReservedCountryInfo> =
+        serde_json::from_slice(raw_data).unwrap();
+    info!(
+        "load_reserved_countries_from_json - loaded {} reserved codes",
+        reserved_map.len()
+    );
+    reserved_map
+}
+
+fn load_postal_codes_from_json() -> HashMap<String, PostalCodeInfo> {
+    info!("load_postal_codes_from_json - loading JSON");
+    let raw_data = include_bytes!("data/postal_codes.json");
+    let postal_map: HashMap<String, PostalCodeInfo> = serde_json::from_slice(raw_data).unwrap();
+    info!(
+        "load_postal_codes_from_json - loaded {} countries",
+        postal_map.len()
+    );
+    postal_map
+}
+
+fn make_postal_patterns() -> HashMap<String, Regex> {
+    info!("make_postal_patterns - compile from POSTAL");
+    POSTAL
+        .values()
+        .map(|postal| {
+            (
+                postal.pattern.to_string(),
+                Regex::new(&format!("^(?:{})$", postal.pattern)).unwrap(),
+            )
+        })
+        .collect()
+}
+
+fn load_country_tlds_from_json() -> HashMap<String, CountryTldInfo> {
+    info!("load_country_tlds_from_json - loading JSON");
+    let raw_data = include_bytes!("data/country_tlds.json");
+    let tld_map: HashMap<String, CountryTldInfo> = serde_json::from_slice(raw_data).unwrap();
+    info!(
+        "load_country_tlds_from_json - loaded {} ccTLDs",
+        tld_map.len()
+    );
+    tld_map
+}
+
+fn make_tld_lookup() -> HashMap<String, String> {
+    info!("make_tld_lookup - create from TLDS");
+    TLDS.values()
+        .filter_map(|tld| {
+            tld.unicode
+                .as_ref()
+                .map(|unicode| (unicode.to_string(), tld.tld.to_string()))
+        })
+        .collect()
+}
+
+fn make_tld_country_lookup() -> HashMap<String, Vec<String>> {
+    info!("make_tld_country_lookup - create from TLDS");
+    let mut lookup_map: HashMap<String, Vec<String>> = HashMap::new();
+    for tld in TLDS.values() {
+        if let Some(country) = tld.country() {
+            lookup_map
+                .entry(country.code.to_string())
+                .or_default()
+                .push(tld.tld.to_string());
+        }
+    }
+    for tlds in lookup_map.values_mut() {
+        tlds.sort_by_key(|tld| (TLDS[tld].is_idn(), tld.to_string()));
+    }
+    info!(
+        "make_tld_country_lookup - mapped {} countries",
+        lookup_map.len()
+    );
+    lookup_map
+}
+
 // ------------------------------------------------------------------------------------------------
 // Unit Tests
 // ------------------------------------------------------------------------------------------------
@@ -124,7 +917,7 @@ mod tests {
     #[test]
     fn test_country_codes() {
         let codes = all_codes();
-        assert!(codes.len() > 0);
+        assert!(!codes.is_empty());
     }
 
     #[test]
@@ -149,11 +942,277 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_country_code_status() {
+        assert_eq!(code_status("DE"), CodeStatus::Assigned);
+        assert_eq!(code_status("DEU"), CodeStatus::Assigned);
+        assert_eq!(code_status("XK"), CodeStatus::PrivateUse);
+        assert_eq!(code_status("XKX"), CodeStatus::PrivateUse);
+        assert_eq!(code_status("AA"), CodeStatus::PrivateUse);
+        assert_eq!(code_status("QM"), CodeStatus::PrivateUse);
+        assert_eq!(code_status("ZZ"), CodeStatus::PrivateUse);
+        assert_eq!(code_status("QA"), CodeStatus::Assigned);
+        assert_eq!(code_status("ZA"), CodeStatus::Assigned);
+        assert_eq!(code_status("QL"), CodeStatus::Unassigned);
+        assert_eq!(code_status("UK"), CodeStatus::Reserved);
+        assert_eq!(code_status("YU"), CodeStatus::Reserved);
+        assert_eq!(code_status("YUG"), CodeStatus::Reserved);
+        assert_eq!(code_status("DD"), CodeStatus::Withdrawn);
+        assert_eq!(code_status("DDDE"), CodeStatus::Withdrawn);
+        assert_eq!(code_status("de"), CodeStatus::Unassigned);
+        assert_eq!(code_status("GERMANY"), CodeStatus::Unassigned);
+    }
+
+    #[test]
+    fn test_good_country_numeric_code() {
+        match lookup_by_numeric(276) {
+            None => panic!("was expecting a country"),
+            Some(country) => assert_eq!(country.code, "DEU"),
+        }
+        assert!(lookup_by_numeric(150).is_none());
+    }
+
+    #[test]
+    fn test_development_status() {
+        let nepal = lookup("NPL").unwrap().development_status().unwrap();
+        assert!(nepal.least_developed);
+        assert!(nepal.land_locked_developing);
+        assert!(!nepal.small_island_developing);
+        assert_eq!(nepal.developed, Some(false));
+        assert!(lookup("DEU")
+            .unwrap()
+            .is_in_group(DevelopmentGroup::Developed));
+        assert!(!lookup("DEU")
+            .unwrap()
+            .is_in_group(DevelopmentGroup::Developing));
+        assert!(lookup("ATA")
+            .unwrap()
+            .development_status()
+            .unwrap()
+            .developed
+            .is_none());
+    }
+
+    #[test]
+    fn test_countries_in_group() {
+        assert_eq!(
+            countries_in_group(DevelopmentGroup::LeastDeveloped).len(),
+            46
+        );
+        assert_eq!(
+            countries_in_group(DevelopmentGroup::LandLockedDeveloping).len(),
+            32
+        );
+        let sids = countries_in_group(DevelopmentGroup::SmallIslandDeveloping);
+        assert!(sids.iter().any(|c| c.code == "FJI"));
+        assert!(!sids.iter().any(|c| c.code == "GBR"));
+    }
+
+    #[test]
+    fn test_former_country_code() {
+        let former = lookup_former("SU").unwrap();
+        assert_eq!(former.code, "SUHH");
+        assert_eq!(former.withdrawn, 1992);
+        assert_eq!(former.successor_countries().len(), 13);
+
+        // CS was used twice, and the most recent wins.
+        assert_eq!(lookup_former("CS").unwrap().code, "CSXX");
+        assert_eq!(lookup_former("CSK").unwrap().code, "CSHH");
+
+        // Yugoslavia -> Serbia and Montenegro -> Montenegro, Serbia
+        let codes: Vec<&str> = lookup_former("YU")
+            .unwrap()
+            .successor_countries()
+            .iter()
+            .map(|c| c.short_code.as_str())
+            .collect();
+        assert_eq!(codes, vec!["ME", "RS"]);
+    }
+
+    #[test]
+    fn test_lookup_with_status() {
+        match lookup_with_status("DEU") {
+            (CodeStatus::Assigned, Some(CountryEntry::Assigned(country))) => {
+                assert_eq!(country.short_code, "DE")
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match lookup_with_status("UK") {
+            (CodeStatus::Reserved, Some(entry @ CountryEntry::Reserved(reserved))) => {
+                assert_eq!(reserved.kind, ReservationKind::Exceptional);
+                assert_eq!(entry.country().unwrap().code, "GBR");
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match lookup_with_status("EU") {
+            (CodeStatus::Reserved, Some(entry)) => assert!(entry.country().is_none()),
+            other => panic!("unexpected {:?}", other),
+        }
+        match lookup_with_status("ZR") {
+            (CodeStatus::Reserved, Some(entry @ CountryEntry::Former(_))) => {
+                assert_eq!(entry.country().unwrap().short_code, "CD")
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match lookup_with_status("DD") {
+            (CodeStatus::Withdrawn, Some(entry)) => {
+                assert_eq!(entry.country().unwrap().short_code, "DE")
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(lookup_with_status("XK").1.is_none());
+        assert!(lookup_with_status("QL").1.is_none());
+    }
+
+    #[test]
+    fn test_country_tlds() {
+        assert!(!all_tlds().is_empty());
+        assert_eq!(country_for_tld("de").unwrap().code, "DEU");
+        assert_eq!(country_for_tld(".UK").unwrap().code, "GBR");
+        assert_eq!(country_for_tld("ac").unwrap().code, "SHN");
+        assert_eq!(country_for_tld("xn--p1ai").unwrap().code, "RUS");
+        assert!(country_for_tld("eu").is_none());
+        assert!(country_for_tld("su").is_none());
+        assert!(country_for_tld("com").is_none());
+
+        let russia = lookup_tld(".рф").unwrap();
+        assert!(russia.is_idn());
+        assert_eq!(russia.tld, "xn--p1ai");
+
+        let uk: Vec<&str> = tlds_for_country("GBR")
+            .iter()
+            .map(|tld| tld.tld.as_str())
+            .collect();
+        assert_eq!(uk, vec!["gb", "uk"]);
+        let china: Vec<&str> = tlds_for_country("CN")
+            .iter()
+            .map(|tld| tld.tld.as_str())
+            .collect();
+        assert_eq!(china, vec!["cn", "xn--fiqs8s", "xn--fiqz9s"]);
+        assert!(tlds_for_country("XX").is_empty());
+        assert!(tlds_for_country("Switzerland").is_empty());
+    }
+
+    #[test]
+    fn test_flag_emoji() {
+        assert_eq!(flag_emoji("DE"), Some("\u{1F1E9}\u{1F1EA}".to_string()));
+        assert_eq!(flag_emoji("DEU"), flag_emoji("DE"));
+        assert_eq!(lookup("JP").unwrap().flag_emoji(), "🇯🇵");
+        assert_eq!(flag_emoji("UN"), Some("🇺🇳".to_string()));
+        assert_eq!(flag_emoji("NAM"), Some("🇳🇦".to_string()));
+        assert_eq!(
+            flag_emoji("GB-SCT"),
+            Some("\u{1F3F4}\u{E0067}\u{E0062}\u{E0073}\u{E0063}\u{E0074}\u{E007F}".to_string())
+        );
+        assert!(flag_emoji("UK").is_none());
+        assert!(flag_emoji("SU").is_none());
+        assert!(flag_emoji("GB-NIR").is_none());
+        assert!(flag_emoji("de").is_none());
+        for code in all_codes() {
+            assert!(flag_emoji(&code).is_some());
+        }
+    }
+
+    #[test]
+    fn test_country_from_flag() {
+        assert_eq!(country_from_flag("🇩🇪").unwrap().code, "DEU");
+        assert_eq!(country_from_flag("🇦🇨").unwrap().code, "SHN");
+        assert_eq!(flag_code("🏴󠁧󠁢󠁷󠁬󠁳󠁿"), Some("GB-WLS".to_string()));
+        assert_eq!(country_from_flag("🏴󠁧󠁢󠁷󠁬󠁳󠁿").unwrap().code, "GBR");
+        assert_eq!(flag_code("🇪🇺"), Some("EU".to_string()));
+        assert!(country_from_flag("🇪🇺").is_none());
+        assert_eq!(flag_code("🇽🇰"), Some("XK".to_string()));
+        assert!(country_from_flag("🇽🇰").is_none());
+        assert!(flag_code("🇽🇦").is_none());
+        assert!(flag_code("🇩🇪🇫🇷").is_none());
+        assert!(flag_code("DE").is_none());
+        assert!(flag_code("🏴").is_none());
+    }
+
+    #[test]
+    fn test_calling_code() {
+        assert_eq!(
+            lookup("DEU").unwrap().calling_code().unwrap().calling_code,
+            49
+        );
+        let canada = lookup("CA").unwrap().calling_code().unwrap();
+        assert_eq!(canada.calling_code, 1);
+        assert!(!canada.main_country_for_code);
+        assert!(lookup("AQ").unwrap().calling_code().is_none());
+    }
+
+    #[test]
+    fn test_postal_code_format() {
+        let germany = postal_code_format("DEU").unwrap();
+        assert_eq!(germany.examples[0], "26133");
+        assert!(germany.is_match("10115"));
+        assert!(!germany.is_match("1011"));
+        assert!(uses_postal_code("NL"));
+        assert!(!uses_postal_code("AE"));
+        assert!(postal_code_format("XX").is_none());
+        assert!(postal_code_format("CHE1").is_none());
+        assert!(!uses_postal_code(""));
+        assert!(!validate_postal_code("Switzerland", "8001"));
+        assert!(normalize_postal_code("CHE1", "8001").is_none());
+        for code in all_codes() {
+            if let Some(postal) = postal_code_format(&code) {
+                for example in &postal.examples {
+                    assert_eq!(postal.normalize(example).as_ref(), Some(example));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_address_format() {
+        let japan = lookup("JPN").unwrap().address_format();
+        assert!(japan.format.starts_with("〒%Z"));
+        assert!(std::ptr::eq(
+            lookup("AQ").unwrap().address_format(),
+            address::default_format()
+        ));
+    }
+
+    #[test]
+    fn test_normalize_postal_code() {
+        assert_eq!(
+            normalize_postal_code("GB", " sw1a1aa "),
+            Some("SW1A 1AA".to_string())
+        );
+        assert_eq!(
+            normalize_postal_code("GB", "M1 1AE"),
+            Some("M1 1AE".to_string())
+        );
+        assert_eq!(
+            normalize_postal_code("NL", "1234ab"),
+            Some("1234 AB".to_string())
+        );
+        assert_eq!(
+            normalize_postal_code("CA", "k1a-0b1"),
+            Some("K1A 0B1".to_string())
+        );
+        assert_eq!(
+            normalize_postal_code("US", "22162 1010"),
+            Some("22162-1010".to_string())
+        );
+        assert_eq!(
+            normalize_postal_code("JP", "1540023"),
+            Some("154-0023".to_string())
+        );
+        assert!(normalize_postal_code("US", "2216").is_none());
+        assert!(normalize_postal_code("GB", "").is_none());
+        assert!(validate_postal_code("SE", "11455"));
+        assert!(!validate_postal_code("SE", "1145"));
+        assert!(!validate_postal_code("HK", "000000"));
+    }
+
     #[test]
     fn test_bad_country_code() {
         match lookup("XXX") {
             None => (),
             Some(_) => panic!("was expecting a None in response"),
         }
+        assert!(lookup("").is_none());
+        assert!(lookup("Switzerland").is_none());
     }
 }
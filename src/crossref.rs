@@ -0,0 +1,285 @@
+/*!
+Cross-reference between ISO 3166 country codes and other country coding schemes.
+
+Many systems identify countries with codes that are not defined by ISO 3166;
+sports results use IOC and FIFA codes, radio and maritime data uses ITU-R
+country symbols, older US government data uses FIPS 10-4 (now GEC) codes,
+vehicle registrations use the codes notified under the UN road traffic
+conventions, and library catalogues use MARC country codes.
+
+Most codes in these schemes correspond to exactly one ISO country, but not
+all; FIFA, for example, has separate codes for England (`ENG`) and Scotland
+(`SCO`) which are both part of the United Kingdom, and FIPS 10-4 has codes
+for the Spratly Islands (`PG`) which have no ISO code at all. Each code
+carries a [`Correspondence`](enum.Correspondence.html) value to identify these.
+
+```
+use locale_codes::crossref::{self, CodeScheme};
+
+let germany = crossref::country_for_code(CodeScheme::Ioc, "GER").unwrap();
+assert_eq!(germany.short_code, "DE");
+
+let fips = crossref::code_for_country(CodeScheme::Fips, "DEU").unwrap();
+assert_eq!(fips.code, "GM");
+```
+
+## Source
+
+The data used here was compiled from the
+[DataHub country-codes](https://datahub.io/core/country-codes) package, the
+[Library of Congress](https://www.loc.gov/marc/countries/), the
+[CIA World Factbook](https://www.cia.gov/the-world-factbook/references/country-data-codes/),
+and the UNECE [Distinguishing Signs of Vehicles](https://unece.org/DAM/trans/conventn/Distinguishing_signs.pdf).
+*/
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::country::{self, CountryInfo};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// The country coding schemes that are cross-referenced to ISO 3166.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CodeScheme {
+    /// International Olympic Committee country codes.
+    Ioc,
+    /// FIFA trigrammes.
+    Fifa,
+    /// ITU-R country symbols.
+    Itu,
+    /// US FIPS PUB 10-4 country codes, withdrawn in 2008.
+    Fips,
+    /// US Geopolitical Entities and Codes, the successor to FIPS 10-4.
+    Gec,
+    /// International vehicle registration codes.
+    Vehicle,
+    /// Library of Congress MARC country codes.
+    Marc,
+}
+
+/// How a code in another scheme corresponds to an ISO 3166 country.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Correspondence {
+    /// The code identifies exactly the ISO country.
+    Exact,
+    /// The code identifies only part of the ISO country.
+    PartOf,
+    /// The code has no corresponding ISO country.
+    NoMatch,
+}
+
+/// A representation of a country code in one of the cross-referenced schemes.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SchemeCodeInfo {
+    /// The scheme this code belongs to.
+    pub scheme: CodeScheme,
+    /// The code, as defined by the scheme.
+    pub code: String,
+    /// The ISO-3166 3-character identifier of the corresponding country,
+    /// `None` if the correspondence is `Correspondence::NoMatch`.
+    pub country_code: Option<String>,
+    /// The name of the area identified, in English, only present where the
+    /// code does not correspond exactly to an ISO country.
+    pub name: Option<String>,
+    /// How this code corresponds to the ISO country.
+    pub correspondence: Correspondence,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+lazy_static! {
+    static ref CODES: Vec<SchemeCodeInfo> = load_xref_from_json();
+    static ref SCHEME_LOOKUP: HashMap<(CodeScheme, String), usize> = make_scheme_lookup();
+    static ref COUNTRY_LOOKUP: HashMap<String, Vec<usize>> = make_country_lookup();
+}
+
+/// Lookup a `SchemeCodeInfo` based on the scheme and it's code within that
+/// scheme, returning `None` if the code is not known.
+pub fn lookup(scheme: CodeScheme, code: &str) -> Option<&'static SchemeCodeInfo> {
+    debug!("crossref::lookup: {:?} {}", scheme, code);
+    match SCHEME_LOOKUP.get(&(scheme, code.to_string())) {
+        Some(index) => Some(&CODES[*index]),
+        None => None,
+    }
+}
+
+/// Lookup the ISO `CountryInfo` for a code in another scheme. Codes that
+/// identify only part of a country will return that country, check the
+/// `correspondence` of the `SchemeCodeInfo` to distinguish these.
+pub fn country_for_code(scheme: CodeScheme, code: &str) -> Option<&'static CountryInfo> {
+    match lookup(scheme, code) {
+        Some(info) => info.country(),
+        None => None,
+    }
+}
+
+/// Lookup the code that corresponds exactly to the identified ISO country
+/// in the given scheme; the country may be identified by it's ISO-3166 2,
+/// or 3, character identifier.
+pub fn code_for_country(scheme: CodeScheme, country_code: &str) -> Option<&'static SchemeCodeInfo> {
+    codes_for_country(country_code)
+        .into_iter()
+        .find(|info| info.scheme == scheme && info.correspondence == Correspondence::Exact)
+}
+
+/// Return all the codes, in all schemes, that correspond to all, or part
+/// of, the identified ISO country.
+pub fn codes_for_country(country_code: &str) -> Vec<&'static SchemeCodeInfo> {
+    let country = match country::lookup(country_code) {
+        Some(country) => country,
+        None => return Vec::new(),
+    };
+    match COUNTRY_LOOKUP.get(&country.code) {
+        Some(indices) => indices.iter().map(|index| &CODES[*index]).collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Return all the codes defined for the given scheme.
+pub fn all_codes(scheme: CodeScheme) -> Vec<String> {
+    CODES
+        .iter()
+        .filter(|info| info.scheme == scheme)
+        .map(|info| info.code.to_string())
+        .collect()
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl SchemeCodeInfo {
+    /// Return the ISO `CountryInfo` that this code corresponds to, if any.
+    pub fn country(&self) -> Option<&'static CountryInfo> {
+        match &self.country_code {
+            Some(code) => country::lookup(code),
+            None => None,
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Generated Data
+// ------------------------------------------------------------------------------------------------
+
+fn load_xref_from_json() -> Vec<SchemeCodeInfo> {
+    info!("load_xref_from_json - loading JSON");
+    let raw_data = include_bytes!("data/country_xref.json");
+    let codes: Vec<SchemeCodeInfo> = serde_json::from_slice(raw_data).unwrap();
+    info!("load_xref_from_json - loaded {} codes", codes.len());
+    codes
+}
+
+fn make_scheme_lookup() -> HashMap<(CodeScheme, String), usize> {
+    info!("make_scheme_lookup - create from CODES");
+    CODES
+        .iter()
+        .enumerate()
+        .map(|(index, info)| ((info.scheme, info.code.to_string()), index))
+        .collect()
+}
+
+fn make_country_lookup() -> HashMap<String, Vec<usize>> {
+    info!("make_country_lookup - create from CODES");
+    let mut lookup_map: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, info) in CODES.iter().enumerate() {
+        if let Some(country_code) = &info.country_code {
+            lookup_map
+                .entry(country_code.to_string())
+                .or_default()
+                .push(index);
+        }
+    }
+    info!(
+        "make_country_lookup - mapped {} countries",
+        lookup_map.len()
+    );
+    lookup_map
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn test_scheme_codes() {
+        for scheme in &[
+            CodeScheme::Ioc,
+            CodeScheme::Fifa,
+            CodeScheme::Itu,
+            CodeScheme::Fips,
+            CodeScheme::Gec,
+            CodeScheme::Vehicle,
+            CodeScheme::Marc,
+        ] {
+            assert!(!all_codes(*scheme).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_good_scheme_code() {
+        assert_eq!(
+            country_for_code(CodeScheme::Ioc, "GER").unwrap().code,
+            "DEU"
+        );
+        assert_eq!(
+            country_for_code(CodeScheme::Fifa, "SUI").unwrap().code,
+            "CHE"
+        );
+        assert_eq!(
+            country_for_code(CodeScheme::Vehicle, "CH").unwrap().code,
+            "CHE"
+        );
+        assert_eq!(
+            country_for_code(CodeScheme::Marc, "xxk").unwrap().code,
+            "GBR"
+        );
+        assert_eq!(country_for_code(CodeScheme::Itu, "G").unwrap().code, "GBR");
+    }
+
+    #[test]
+    fn test_partial_scheme_code() {
+        let england = lookup(CodeScheme::Fifa, "ENG").unwrap();
+        assert_eq!(england.correspondence, Correspondence::PartOf);
+        assert_eq!(england.country().unwrap().code, "GBR");
+
+        let kosovo = lookup(CodeScheme::Ioc, "KOS").unwrap();
+        assert_eq!(kosovo.correspondence, Correspondence::NoMatch);
+        assert!(kosovo.country().is_none());
+    }
+
+    #[test]
+    fn test_codes_for_country() {
+        assert_eq!(code_for_country(CodeScheme::Fips, "DE").unwrap().code, "GM");
+        assert_eq!(code_for_country(CodeScheme::Gec, "SSD").unwrap().code, "OD");
+        assert!(code_for_country(CodeScheme::Fips, "SSD").is_none());
+        // FIFA has no single code for the United Kingdom
+        assert!(code_for_country(CodeScheme::Fifa, "GB").is_none());
+        let fifa: Vec<&str> = codes_for_country("GB")
+            .iter()
+            .filter(|info| info.scheme == CodeScheme::Fifa)
+            .map(|info| info.code.as_str())
+            .collect();
+        assert_eq!(fifa, vec!["ENG", "SCO", "WAL", "NIR"]);
+    }
+
+    #[test]
+    fn test_bad_scheme_code() {
+        assert!(lookup(CodeScheme::Ioc, "XXX").is_none());
+        assert!(codes_for_country("XX").is_empty());
+        assert!(codes_for_country("").is_empty());
+        assert!(codes_for_country("Germany").is_empty());
+        assert!(code_for_country(CodeScheme::Fifa, "").is_none());
+    }
+}
@@ -18,6 +18,8 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::CodeStatus;
+
 // ------------------------------------------------------------------------------------------------
 // Public Types
 // ------------------------------------------------------------------------------------------------
@@ -77,7 +79,7 @@ pub fn lookup_by_alpha(alphabetic_code: &str) -> Option<&'static CurrencyInfo> {
 /// Lookup a `CurrencyInfo` based on it's ISO-4217 numeric identifier,
 /// returning `None` if the name does not exist in the current ISO data set.
 pub fn lookup_by_numeric(numeric_code: &u16) -> Option<&'static CurrencyInfo> {
-    match NUMERIC_LOOKUP.get(&numeric_code) {
+    match NUMERIC_LOOKUP.get(numeric_code) {
         Some(v) => lookup_by_alpha(v),
         None => None,
     }
@@ -92,6 +94,23 @@ pub fn currencies_for_country_name(name: &str) -> Vec<&'static CurrencyInfo> {
         .collect()
 }
 
+/// Classify an ISO-4217 3-character identifier. Codes beginning with `X`
+/// are reserved for supranational currencies, precious metals, and other
+/// special uses, any such code not currently assigned is reported as
+/// `CodeStatus::Reserved`.
+pub fn code_status(alphabetic_code: &str) -> CodeStatus {
+    let bytes = alphabetic_code.as_bytes();
+    if bytes.len() != 3 || !bytes.iter().all(u8::is_ascii_uppercase) {
+        CodeStatus::Unassigned
+    } else if CURRENCIES.contains_key(alphabetic_code) {
+        CodeStatus::Assigned
+    } else if bytes[0] == b'X' {
+        CodeStatus::Reserved
+    } else {
+        CodeStatus::Unassigned
+    }
+}
+
 /// Return all the registered ISO-4217 3-character currency codes.
 pub fn all_alpha_codes() -> Vec<String> {
     CURRENCIES.keys().cloned().collect()
@@ -145,7 +164,7 @@ mod tests {
     // --------------------------------------------------------------------------------------------
     #[test]
     fn test_currency_loading() {
-        match lookup_by_alpha(&"GBP".to_string()) {
+        match lookup_by_alpha("GBP") {
             None => println!("lookup_by_alpha NO 'GBP'"),
             Some(c) => println!("lookup_by_alpha {:#?}", to_string_pretty(c)),
         }
@@ -155,9 +174,9 @@ mod tests {
     #[test]
     fn test_currency_codes() {
         let codes = all_alpha_codes();
-        assert!(codes.len() > 0);
+        assert!(!codes.is_empty());
         let numerics = all_numeric_codes();
-        assert!(numerics.len() > 0);
+        assert!(!numerics.is_empty());
     }
 
     #[test]
@@ -170,12 +189,21 @@ mod tests {
 
     #[test]
     fn test_bad_currency_code() {
-        match lookup_by_alpha(&"ZZZ") {
+        match lookup_by_alpha("ZZZ") {
             None => (),
             Some(_) => panic!("was expecting a None in response"),
         }
     }
 
+    #[test]
+    fn test_currency_code_status() {
+        assert_eq!(code_status("GBP"), CodeStatus::Assigned);
+        assert_eq!(code_status("XAU"), CodeStatus::Assigned);
+        assert_eq!(code_status("XBT"), CodeStatus::Reserved);
+        assert_eq!(code_status("ZZZ"), CodeStatus::Unassigned);
+        assert_eq!(code_status("gbp"), CodeStatus::Unassigned);
+    }
+
     #[test]
     fn test_for_country() {
         let currencies = currencies_for_country_name("Mexico");
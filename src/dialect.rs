@@ -0,0 +1,984 @@
+/*!
+Conversions between the locale identifier dialects used by different
+platforms.
+
+The same locale is written differently by each platform; Serbian, in the
+Latin script, as spoken in Serbia, is:
+
+| Dialect      | Identifier                         |
+|--------------|------------------------------------|
+| BCP 47       | `sr-Latn-RS`                       |
+| POSIX        | `sr_RS@latin`                      |
+| ICU          | `sr_Latn_RS`                       |
+| Java         | `sr_RS_#Latn`                      |
+| Windows LCID | `0x241A`                           |
+| Android      | `values-b+sr+Latn+RS`              |
+
+A [`LocaleIdentifier`](struct.LocaleIdentifier.html) can be parsed from,
+and formatted to, any of these dialects, and the language, script, region
+and variant subtags are validated against the
+[`language`](../language/index.html), [`script`](../script/index.html),
+[`country`](../country/index.html) and [`region`](../region/index.html)
+registries. Not every dialect can represent every part of an identifier,
+POSIX has no script subtag for example, and so each conversion returns the
+parts that were lost along with the converted value.
+
+```
+use locale_codes::dialect::{self, ConversionLoss, LocaleDialect};
+
+let converted = dialect::convert(
+    "sr_Latn_RS@calendar=gregorian",
+    LocaleDialect::Icu,
+    LocaleDialect::Java,
+).unwrap();
+assert_eq!(converted.value, "sr_RS_#Latn-u-ca-gregory");
+assert!(converted.is_lossless());
+
+let converted = dialect::convert("0x0C1A", LocaleDialect::WindowsLcid, LocaleDialect::Posix);
+assert!(converted.is_none());
+
+let converted = dialect::convert(
+    "uz-Cyrl-UZ",
+    LocaleDialect::Bcp47,
+    LocaleDialect::Posix,
+).unwrap();
+assert_eq!(converted.value, "uz_UZ@cyrillic");
+
+let converted = dialect::convert(
+    "zh-Hant-SG-u-nu-hanidec",
+    LocaleDialect::Bcp47,
+    LocaleDialect::Posix,
+).unwrap();
+assert_eq!(converted.value, "zh_SG");
+assert_eq!(
+    converted.losses,
+    vec![
+        ConversionLoss::Script("Hant".to_string()),
+        ConversionLoss::Keyword("nu".to_string(), "hanidec".to_string()),
+    ]
+);
+```
+
+## Source - MS-LCID
+
+The table of Windows locale identifiers is taken from the Microsoft
+[[MS-LCID]](https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-lcid/70feba9f-294e-491e-b6eb-56532684c37f)
+reference.
+*/
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use serde::Deserialize;
+
+use crate::canonical::is_variant;
+use crate::likely_subtags::{
+    self, normalize_country, normalize_language, normalize_script, LocaleSubtags,
+};
+use crate::posix::PosixLocale;
+use crate::region;
+use crate::unicode_extension::{is_key, is_type, parse_keywords, UnicodeExtension};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// The dialects, or platform conventions, in which a locale identifier may
+/// be written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LocaleDialect {
+    /// An IETF BCP 47 language tag, `sr-Latn-RS-u-ca-gregory`.
+    Bcp47,
+    /// A POSIX locale name, `sr_RS.UTF-8@latin`.
+    Posix,
+    /// An ICU locale identifier, `sr_Latn_RS@calendar=gregorian`.
+    Icu,
+    /// The result of the Java `Locale.toString()` method, `sr_RS_#Latn`.
+    Java,
+    /// A Windows locale identifier (LCID), written in hexadecimal as
+    /// `0x241A`, or in decimal.
+    WindowsLcid,
+    /// An Android resource directory qualifier, `values-b+sr+Latn+RS` or
+    /// `values-en-rUS`; the `values-` prefix is optional when parsing.
+    Android,
+}
+
+/// A locale identifier, independent of any dialect.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocaleIdentifier {
+    /// The ISO-639 language code, the 2-character form where one exists.
+    pub language: String,
+    /// The ISO-15924 script code.
+    pub script: Option<String>,
+    /// The ISO-3166 2-character country code, or a UN M.49 numeric region
+    /// code.
+    pub region: Option<String>,
+    /// Any BCP 47 variant subtags, in lower case.
+    pub variants: Vec<String>,
+    /// Any Unicode locale extension keywords, using the BCP 47 key and type
+    /// names; for example `ca` and `gregory`.
+    pub keywords: BTreeMap<String, String>,
+    /// A POSIX codeset, such as `UTF-8`.
+    pub codeset: Option<String>,
+    /// A POSIX modifier that has no equivalent subtag, such as `saaho`.
+    pub modifier: Option<String>,
+}
+
+/// A part of a locale identifier that could not be represented in the
+/// dialect it was converted to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConversionLoss {
+    /// The script subtag.
+    Script(String),
+    /// The region subtag.
+    Region(String),
+    /// A variant subtag.
+    Variant(String),
+    /// A Unicode locale extension keyword, and its type.
+    Keyword(String, String),
+    /// The POSIX codeset.
+    Codeset(String),
+    /// The POSIX modifier.
+    Modifier(String),
+}
+
+/// The result of converting a locale identifier to a dialect, along with
+/// any parts of the identifier that were lost.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Conversion<T> {
+    /// The converted value.
+    pub value: T,
+    /// The parts of the locale identifier that were not represented in the
+    /// value.
+    pub losses: Vec<ConversionLoss>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+lazy_static! {
+    static ref TAG_BY_LCID: HashMap<u32, String> = load_lcid_from_json();
+    static ref LCID_BY_TAG: HashMap<String, u32> = make_lcid_lookup();
+}
+
+/// Convert a locale identifier from one dialect to another, returning
+/// `None` if the value is not valid in the `from` dialect, or if it has no
+/// representation in the `to` dialect.
+pub fn convert(value: &str, from: LocaleDialect, to: LocaleDialect) -> Option<Conversion<String>> {
+    debug!("dialect::convert: {} {:?} -> {:?}", value, from, to);
+    LocaleIdentifier::parse(value, from)?.format(to)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for LocaleDialect {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "{}",
+            match self {
+                LocaleDialect::Bcp47 => "BCP 47",
+                LocaleDialect::Posix => "POSIX",
+                LocaleDialect::Icu => "ICU",
+                LocaleDialect::Java => "Java",
+                LocaleDialect::WindowsLcid => "Windows LCID",
+                LocaleDialect::Android => "Android",
+            }
+        )
+    }
+}
+
+impl Display for ConversionLoss {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            ConversionLoss::Script(script) => write!(f, "script {}", script),
+            ConversionLoss::Region(region) => write!(f, "region {}", region),
+            ConversionLoss::Variant(variant) => write!(f, "variant {}", variant),
+            ConversionLoss::Keyword(key, value) => write!(f, "keyword {}={}", key, value),
+            ConversionLoss::Codeset(codeset) => write!(f, "codeset {}", codeset),
+            ConversionLoss::Modifier(modifier) => write!(f, "modifier {}", modifier),
+        }
+    }
+}
+
+impl<T> Conversion<T> {
+    /// Returns `true` if no part of the locale identifier was lost.
+    pub fn is_lossless(&self) -> bool {
+        self.losses.is_empty()
+    }
+}
+
+impl Display for LocaleIdentifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.to_bcp47().value)
+    }
+}
+
+impl LocaleIdentifier {
+    /// Construct a new identifier from a language and optional script and
+    /// region, returning `None` if any are not present in their registry.
+    pub fn new(language: &str, script: Option<&str>, region: Option<&str>) -> Option<Self> {
+        LocaleIdentifier::build(language, script, region, &[], BTreeMap::new())
+    }
+
+    /// Parse an identifier in the given dialect, returning `None` if it is
+    /// not valid.
+    pub fn parse(value: &str, dialect: LocaleDialect) -> Option<Self> {
+        match dialect {
+            LocaleDialect::Bcp47 => LocaleIdentifier::parse_bcp47(value),
+            LocaleDialect::Posix => LocaleIdentifier::parse_posix(value),
+            LocaleDialect::Icu => LocaleIdentifier::parse_icu(value),
+            LocaleDialect::Java => LocaleIdentifier::parse_java(value),
+            LocaleDialect::WindowsLcid => {
+                let lcid = match value.strip_prefix("0x").or(value.strip_prefix("0X")) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => value.parse::<u32>().ok()?,
+                };
+                LocaleIdentifier::from_lcid(lcid)
+            }
+            LocaleDialect::Android => LocaleIdentifier::parse_android(value),
+        }
+    }
+
+    /// Parse a BCP 47 language tag; the only extension supported is the
+    /// Unicode locale (`-u-`) extension.
+    pub fn parse_bcp47(tag: &str) -> Option<Self> {
+        let parts: Vec<&str> = tag.split('-').collect();
+        let (base, extension) = parts.split_at(
+            parts
+                .iter()
+                .position(|part| part.len() == 1)
+                .unwrap_or(parts.len()),
+        );
+        let keywords = match extension.split_first() {
+            None => BTreeMap::new(),
+            Some((singleton, keywords)) if singleton.eq_ignore_ascii_case("u") => {
+                parse_keywords(keywords)?
+            }
+            Some(_) => return None,
+        };
+        LocaleIdentifier::from_subtags(base, keywords)
+    }
+
+    /// Parse a POSIX locale name, `language[_territory][.codeset][@modifier]`;
+    /// the modifiers `latin`, `cyrillic` and `devanagari` are taken as the
+    /// script, `valencia` as a variant, and `euro` as the `cu-eur` keyword.
+    pub fn parse_posix(name: &str) -> Option<Self> {
+        let posix = PosixLocale::parse(name)?;
+        let mut identifier =
+            LocaleIdentifier::new(&posix.language, None, posix.territory.as_deref())?;
+        identifier.codeset = posix.codeset;
+        if let Some(modifier) = posix.modifier {
+            let modifier = modifier.to_ascii_lowercase();
+            if let Some((_, script)) = SCRIPT_MODIFIERS.iter().find(|(name, _)| *name == modifier) {
+                identifier.script = Some(script.to_string());
+            } else if VARIANT_MODIFIERS.contains(&modifier.as_str()) {
+                identifier.variants.push(modifier);
+            } else if modifier == EURO_MODIFIER {
+                identifier
+                    .keywords
+                    .insert("cu".to_string(), "eur".to_string());
+            } else {
+                identifier.modifier = Some(modifier);
+            }
+        }
+        Some(identifier)
+    }
+
+    /// Parse an ICU locale identifier,
+    /// `language[_Script][_REGION][_VARIANT][@key=value;...]`.
+    pub fn parse_icu(id: &str) -> Option<Self> {
+        let (base, keywords) = match id.split_once('@') {
+            Some((base, keywords)) => (base, Some(keywords)),
+            None => (id, None),
+        };
+        let mut parts: Vec<&str> = base.split(['_', '-']).collect();
+        // a variant without a region is written `en__POSIX`.
+        if parts.len() > 2 && parts[1].is_empty() {
+            parts.remove(1);
+        } else if parts.len() > 3 && parts[2].is_empty() && parts[1].len() == 4 {
+            parts.remove(2);
+        }
+        let mut parsed = BTreeMap::new();
+        for keyword in keywords.iter().flat_map(|keywords| keywords.split(';')) {
+            let (key, value) = keyword.split_once('=')?;
+            let key = key.trim().to_ascii_lowercase();
+            let (key, value) = match ICU_KEYWORDS.iter().find(|(_, name)| *name == key) {
+                Some((bcp47_key, _)) => {
+                    let value = value.trim().to_ascii_lowercase();
+                    let value = ICU_VALUES
+                        .iter()
+                        .find(|(k, _, name)| k == bcp47_key && *name == value)
+                        .map(|(_, bcp47_value, _)| bcp47_value.to_string())
+                        .unwrap_or(value);
+                    (bcp47_key.to_string(), value)
+                }
+                None => (key, value.trim().to_string()),
+            };
+            if key.is_empty() || value.is_empty() {
+                return None;
+            }
+            parsed.insert(key, value);
+        }
+        LocaleIdentifier::from_subtags(&parts, parsed)
+    }
+
+    /// Parse the result of the Java `Locale.toString()` method,
+    /// `language[_COUNTRY][_variant][_#Script][-extensions]`; the legacy
+    /// language codes `iw`, `in` and `ji` are accepted.
+    pub fn parse_java(value: &str) -> Option<Self> {
+        let (base, tail) = match value.split_once("_#") {
+            Some((base, tail)) => (base, Some(tail)),
+            None => (value, None),
+        };
+        let mut fields = base.splitn(3, '_');
+        let language = legacy_language(fields.next()?);
+        let country = fields.next().filter(|country| !country.is_empty());
+        let variants: Vec<&str> = fields
+            .next()
+            .map(|variants| variants.split(['_', '-']).collect())
+            .unwrap_or_default();
+        let (script, keywords) = match tail {
+            None => (None, BTreeMap::new()),
+            Some(tail) => {
+                let mut parts: Vec<&str> = tail.split('-').collect();
+                let script = if parts[0].len() == 4 {
+                    Some(parts.remove(0))
+                } else {
+                    None
+                };
+                let keywords = match parts.split_first() {
+                    None => BTreeMap::new(),
+                    Some((singleton, keywords)) if *singleton == "u" => parse_keywords(keywords)?,
+                    Some(_) => return None,
+                };
+                (script, keywords)
+            }
+        };
+        LocaleIdentifier::build(language, script, country, &variants, keywords)
+    }
+
+    /// Parse an Android resource qualifier, either `values-en-rUS` or the
+    /// BCP 47 form `values-b+sr+Latn+RS`; the `values-` prefix is optional.
+    pub fn parse_android(qualifier: &str) -> Option<Self> {
+        let qualifier = qualifier.strip_prefix("values-").unwrap_or(qualifier);
+        if let Some(tag) = qualifier.strip_prefix("b+") {
+            let parts: Vec<&str> = tag.split('+').collect();
+            LocaleIdentifier::from_subtags(&parts, BTreeMap::new())
+        } else {
+            let (language, region) = match qualifier.split_once('-') {
+                Some((language, region)) => (language, Some(region.strip_prefix('r')?)),
+                None => (qualifier, None),
+            };
+            if region.is_some_and(|region| region.len() != 2) {
+                return None;
+            }
+            LocaleIdentifier::new(legacy_language(language), None, region)
+        }
+    }
+
+    /// Return the identifier for a Windows locale identifier, `None` if the
+    /// LCID is not in the table. Any sort identifier, in bits 16-19, is
+    /// ignored.
+    pub fn from_lcid(lcid: u32) -> Option<Self> {
+        LocaleIdentifier::parse_bcp47(TAG_BY_LCID.get(&(lcid & 0xFFFF))?)
+    }
+
+    /// Return the Unicode locale extension keywords, for validation against
+    /// the [`unicode_extension`](../unicode_extension/index.html) registry.
+    pub fn extension(&self) -> UnicodeExtension {
+        UnicodeExtension::from_keywords(self.keywords.clone())
+    }
+
+    /// Format the identifier in the given dialect, `None` if it has no
+    /// representation in that dialect.
+    pub fn format(&self, dialect: LocaleDialect) -> Option<Conversion<String>> {
+        match dialect {
+            LocaleDialect::Bcp47 => Some(self.to_bcp47()),
+            LocaleDialect::Posix => Some(self.to_posix()),
+            LocaleDialect::Icu => Some(self.to_icu()),
+            LocaleDialect::Java => Some(self.to_java()),
+            LocaleDialect::WindowsLcid => self.to_lcid().map(|conversion| Conversion {
+                value: format!("0x{:04X}", conversion.value),
+                losses: conversion.losses,
+            }),
+            LocaleDialect::Android => Some(self.to_android()),
+        }
+    }
+
+    /// Format the identifier as a BCP 47 language tag.
+    pub fn to_bcp47(&self) -> Conversion<String> {
+        let mut losses = Vec::new();
+        let mut value = self.base_subtags("-");
+        for variant in &self.variants {
+            value.push_str(&format!("-{}", variant));
+        }
+        value.push_str(&self.extension_subtags(&mut losses));
+        self.posix_losses(&mut losses);
+        Conversion { value, losses }
+    }
+
+    /// Format the identifier as a POSIX locale name; a script, or variant,
+    /// with an equivalent modifier is written as the modifier. The script
+    /// is omitted where it is the likely script for the language and region.
+    pub fn to_posix(&self) -> Conversion<String> {
+        let mut losses = Vec::new();
+        let mut value = self.language.clone();
+        let mut modifier = self.modifier.clone();
+        if let Some(script) = self
+            .script
+            .as_ref()
+            .filter(|_| self.script != self.likely_script())
+        {
+            match SCRIPT_MODIFIERS.iter().find(|(_, s)| s == script) {
+                Some((name, _)) if modifier.is_none() => modifier = Some(name.to_string()),
+                _ => losses.push(ConversionLoss::Script(script.clone())),
+            }
+        }
+        match &self.region {
+            Some(region) if is_numeric_region(region) => {
+                losses.push(ConversionLoss::Region(region.clone()))
+            }
+            Some(region) => value.push_str(&format!("_{}", region)),
+            None => (),
+        }
+        for variant in &self.variants {
+            if modifier.is_none() && VARIANT_MODIFIERS.contains(&variant.as_str()) {
+                modifier = Some(variant.clone());
+            } else {
+                losses.push(ConversionLoss::Variant(variant.clone()));
+            }
+        }
+        for (key, type_value) in &self.keywords {
+            if modifier.is_none() && key == "cu" && type_value == "eur" {
+                modifier = Some(EURO_MODIFIER.to_string());
+            } else {
+                losses.push(ConversionLoss::Keyword(key.clone(), type_value.clone()));
+            }
+        }
+        if let Some(codeset) = &self.codeset {
+            value.push_str(&format!(".{}", codeset));
+        }
+        if let Some(modifier) = modifier {
+            value.push_str(&format!("@{}", modifier));
+        }
+        Conversion { value, losses }
+    }
+
+    /// Format the identifier as an ICU locale identifier.
+    pub fn to_icu(&self) -> Conversion<String> {
+        let mut losses = Vec::new();
+        let mut value = self.base_subtags("_");
+        if !self.variants.is_empty() {
+            if self.region.is_none() {
+                value.push('_');
+            }
+            for variant in &self.variants {
+                value.push_str(&format!("_{}", variant.to_ascii_uppercase()));
+            }
+        }
+        let keywords: Vec<String> = self
+            .keywords
+            .iter()
+            .map(
+                |(key, type_value)| match ICU_KEYWORDS.iter().find(|(k, _)| k == key) {
+                    Some((_, name)) => {
+                        let type_value = ICU_VALUES
+                            .iter()
+                            .find(|(k, v, _)| k == key && v == type_value)
+                            .map(|(_, _, icu_value)| icu_value.to_string())
+                            .unwrap_or_else(|| type_value.clone());
+                        if key == "cu" {
+                            format!("{}={}", name, type_value.to_ascii_uppercase())
+                        } else {
+                            format!("{}={}", name, type_value)
+                        }
+                    }
+                    None => format!("{}={}", key, type_value),
+                },
+            )
+            .collect();
+        if !keywords.is_empty() {
+            value.push_str(&format!("@{}", keywords.join(";")));
+        }
+        self.posix_losses(&mut losses);
+        Conversion { value, losses }
+    }
+
+    /// Format the identifier in the form returned by the Java
+    /// `Locale.toString()` method.
+    pub fn to_java(&self) -> Conversion<String> {
+        let mut losses = Vec::new();
+        let mut value = self.language.clone();
+        let extension = self.extension_subtags(&mut losses);
+        let has_tail = self.script.is_some() || !extension.is_empty();
+        if self.region.is_some() || !self.variants.is_empty() || has_tail {
+            value.push_str(&format!("_{}", self.region.as_deref().unwrap_or_default()));
+        }
+        if !self.variants.is_empty() {
+            value.push_str(&format!("_{}", self.variants.join("_")));
+        }
+        if has_tail {
+            value.push_str("_#");
+            match &self.script {
+                Some(script) => {
+                    value.push_str(script);
+                    value.push_str(&extension);
+                }
+                None => value.push_str(extension.trim_start_matches('-')),
+            }
+        }
+        self.posix_losses(&mut losses);
+        Conversion { value, losses }
+    }
+
+    /// Format the identifier as an Android resource directory name; the
+    /// `values-b+` form is used where there is a script, variant or
+    /// numeric region.
+    pub fn to_android(&self) -> Conversion<String> {
+        let mut losses = Vec::new();
+        let value = if self.script.is_none()
+            && self.variants.is_empty()
+            && !self.region.as_deref().is_some_and(is_numeric_region)
+        {
+            match &self.region {
+                Some(region) => format!("values-{}-r{}", self.language, region),
+                None => format!("values-{}", self.language),
+            }
+        } else {
+            let mut value = format!("values-b+{}", self.base_subtags("+"));
+            for variant in &self.variants {
+                value.push_str(&format!("+{}", variant));
+            }
+            value
+        };
+        for (key, type_value) in &self.keywords {
+            losses.push(ConversionLoss::Keyword(key.clone(), type_value.clone()));
+        }
+        self.posix_losses(&mut losses);
+        Conversion { value, losses }
+    }
+
+    /// Return the Windows locale identifier (LCID), `None` if there is no
+    /// LCID for the language. Where there is no LCID for the script or
+    /// region the language-neutral identifier is returned, and the script
+    /// or region reported as lost.
+    pub fn to_lcid(&self) -> Option<Conversion<u32>> {
+        // the script may be implicit in the table, `zh-TW` rather than
+        // `zh-Hant-TW`, or explicit where the identifier's is not, `sr-RS`
+        // rather than `sr-Cyrl-RS`.
+        let likely_script = self.likely_script();
+        let script_implied = self.script.is_none() || self.script == likely_script;
+        let mut scripts = vec![self.script.as_deref()];
+        if script_implied {
+            scripts.push(likely_script.as_deref());
+            scripts.push(None);
+        }
+        let region = self.region.as_deref();
+        let region_lost = region.is_some();
+        let candidates = scripts
+            .iter()
+            .map(|script| (*script, region, false, false))
+            .chain(
+                scripts
+                    .iter()
+                    .map(|script| (*script, None, false, region_lost)),
+            )
+            .chain(std::iter::once((None, None, !script_implied, region_lost)));
+        let extension = self.extension_subtags(&mut Vec::new());
+        for (script, region, script_lost, region_lost) in candidates {
+            let mut tag = self.language.clone();
+            for subtag in [script, region].iter().flatten() {
+                tag.push_str(&format!("-{}", subtag));
+            }
+            if !extension.is_empty() {
+                if let Some(lcid) = LCID_BY_TAG.get(&format!("{}{}", tag, extension)) {
+                    return Some(Conversion {
+                        value: *lcid,
+                        losses: self.lcid_losses(script_lost, region_lost, false),
+                    });
+                }
+            }
+            if let Some(lcid) = LCID_BY_TAG.get(&tag) {
+                return Some(Conversion {
+                    value: *lcid,
+                    losses: self.lcid_losses(script_lost, region_lost, true),
+                });
+            }
+        }
+        None
+    }
+
+    fn build(
+        language: &str,
+        script: Option<&str>,
+        region: Option<&str>,
+        variants: &[&str],
+        keywords: BTreeMap<String, String>,
+    ) -> Option<Self> {
+        let mut normalized_variants: Vec<String> = Vec::new();
+        for variant in variants {
+            let variant = variant.to_ascii_lowercase();
+            if !is_variant(&variant) || normalized_variants.contains(&variant) {
+                return None;
+            }
+            normalized_variants.push(variant);
+        }
+        Some(LocaleIdentifier {
+            language: normalize_language(language)?,
+            script: match script {
+                Some(script) => Some(normalize_script(script)?),
+                None => None,
+            },
+            region: match region {
+                Some(region) if is_numeric_region(region) => {
+                    region::lookup(region.parse::<u16>().ok()?)?;
+                    Some(region.to_string())
+                }
+                Some(region) if region.len() == 2 => Some(normalize_country(region)?),
+                Some(_) => return None,
+                None => None,
+            },
+            variants: normalized_variants,
+            keywords,
+            codeset: None,
+            modifier: None,
+        })
+    }
+
+    fn from_subtags(subtags: &[&str], keywords: BTreeMap<String, String>) -> Option<Self> {
+        let (language, mut rest) = subtags.split_first()?;
+        let mut script = None;
+        let mut region = None;
+        if let Some((subtag, tail)) = rest.split_first() {
+            if subtag.len() == 4 && subtag.chars().all(|c| c.is_ascii_alphabetic()) {
+                script = Some(*subtag);
+                rest = tail;
+            }
+        }
+        if let Some((subtag, tail)) = rest.split_first() {
+            if (subtag.len() == 2 && subtag.chars().all(|c| c.is_ascii_alphabetic()))
+                || is_numeric_region(subtag)
+            {
+                region = Some(*subtag);
+                rest = tail;
+            }
+        }
+        LocaleIdentifier::build(language, script, region, rest, keywords)
+    }
+
+    fn base_subtags(&self, separator: &str) -> String {
+        let mut value = self.language.clone();
+        if let Some(script) = &self.script {
+            value.push_str(separator);
+            value.push_str(script);
+        }
+        if let Some(region) = &self.region {
+            value.push_str(separator);
+            value.push_str(region);
+        }
+        value
+    }
+
+    fn extension_subtags(&self, losses: &mut Vec<ConversionLoss>) -> String {
+        let mut extension = String::new();
+        for (key, value) in &self.keywords {
+            if !is_key(key) || (value != "true" && !is_type(value)) {
+                losses.push(ConversionLoss::Keyword(key.clone(), value.clone()));
+            } else if value == "true" {
+                extension.push_str(&format!("-{}", key));
+            } else {
+                extension.push_str(&format!("-{}-{}", key, value));
+            }
+        }
+        if extension.is_empty() {
+            extension
+        } else {
+            format!("-u{}", extension)
+        }
+    }
+
+    fn posix_losses(&self, losses: &mut Vec<ConversionLoss>) {
+        if let Some(codeset) = &self.codeset {
+            losses.push(ConversionLoss::Codeset(codeset.clone()));
+        }
+        if let Some(modifier) = &self.modifier {
+            losses.push(ConversionLoss::Modifier(modifier.clone()));
+        }
+    }
+
+    fn lcid_losses(&self, script: bool, region: bool, keywords: bool) -> Vec<ConversionLoss> {
+        let mut losses = Vec::new();
+        if script {
+            losses.extend(self.script.iter().cloned().map(ConversionLoss::Script));
+        }
+        if region {
+            losses.extend(self.region.iter().cloned().map(ConversionLoss::Region));
+        }
+        losses.extend(self.variants.iter().cloned().map(ConversionLoss::Variant));
+        if keywords {
+            for (key, value) in &self.keywords {
+                losses.push(ConversionLoss::Keyword(key.clone(), value.clone()));
+            }
+        }
+        self.posix_losses(&mut losses);
+        losses
+    }
+
+    fn likely_script(&self) -> Option<String> {
+        let region = self
+            .region
+            .as_deref()
+            .filter(|region| !is_numeric_region(region));
+        likely_subtags::maximize(&self.language, None, region)
+            .and_then(|subtags: LocaleSubtags| subtags.script)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+struct LcidEntry {
+    lcid: u32,
+    tag: String,
+}
+
+const SCRIPT_MODIFIERS: [(&str, &str); 3] = [
+    ("latin", "Latn"),
+    ("cyrillic", "Cyrl"),
+    ("devanagari", "Deva"),
+];
+
+const VARIANT_MODIFIERS: [&str; 1] = ["valencia"];
+
+const EURO_MODIFIER: &str = "euro";
+
+const ICU_KEYWORDS: [(&str, &str); 6] = [
+    ("ca", "calendar"),
+    ("co", "collation"),
+    ("cu", "currency"),
+    ("hc", "hours"),
+    ("ms", "measure"),
+    ("nu", "numbers"),
+];
+
+const ICU_VALUES: [(&str, &str, &str); 3] = [
+    ("ca", "gregory", "gregorian"),
+    ("co", "phonebk", "phonebook"),
+    ("co", "trad", "traditional"),
+];
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn is_numeric_region(region: &str) -> bool {
+    region.len() == 3 && region.chars().all(|c| c.is_ascii_digit())
+}
+
+fn legacy_language(language: &str) -> &str {
+    match language {
+        "iw" => "he",
+        "in" => "id",
+        "ji" => "yi",
+        _ => language,
+    }
+}
+
+fn make_lcid_lookup() -> HashMap<String, u32> {
+    TAG_BY_LCID
+        .iter()
+        .map(|(lcid, tag)| (tag.to_string(), *lcid))
+        .collect()
+}
+
+// ------------------------------------------------------------------------------------------------
+// Generated Data
+// ------------------------------------------------------------------------------------------------
+
+fn load_lcid_from_json() -> HashMap<u32, String> {
+    info!("load_lcid_from_json - loading JSON");
+    let raw_data = include_bytes!("data/lcid.json");
+    let entries: Vec<LcidEntry> = serde_json::from_slice(raw_data).unwrap();
+    let lcid_map: HashMap<u32, String> = entries
+        .into_iter()
+        .map(|entry| (entry.lcid, entry.tag))
+        .collect();
+    info!(
+        "load_lcid_from_json - loaded {} identifiers",
+        lcid_map.len()
+    );
+    lcid_map
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert_lossless(value: &str, from: LocaleDialect, to: LocaleDialect) -> String {
+        let converted = convert(value, from, to).unwrap();
+        assert!(converted.is_lossless(), "{:?}", converted.losses);
+        converted.value
+    }
+
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn test_parse_dialects() {
+        let expected = LocaleIdentifier::new("sr", Some("Latn"), Some("RS")).unwrap();
+        for (value, dialect) in [
+            ("sr-Latn-RS", LocaleDialect::Bcp47),
+            ("sr_RS@latin", LocaleDialect::Posix),
+            ("sr_Latn_RS", LocaleDialect::Icu),
+            ("sr_RS_#Latn", LocaleDialect::Java),
+            ("0x241A", LocaleDialect::WindowsLcid),
+            ("9242", LocaleDialect::WindowsLcid),
+            ("values-b+sr+Latn+RS", LocaleDialect::Android),
+        ] {
+            assert_eq!(
+                LocaleIdentifier::parse(value, dialect),
+                Some(expected.clone()),
+                "{} {}",
+                dialect,
+                value
+            );
+        }
+        let parsed = LocaleIdentifier::parse_android("values-iw-rIL").unwrap();
+        assert_eq!(parsed.to_string(), "he-IL");
+        let parsed = LocaleIdentifier::parse_icu("sr_Latn_RS@calendar=gregorian").unwrap();
+        assert_eq!(parsed.keywords.get("ca"), Some(&"gregory".to_string()));
+        let parsed = LocaleIdentifier::parse_icu("en__POSIX").unwrap();
+        assert_eq!(parsed.variants, vec!["posix"]);
+        let parsed = LocaleIdentifier::parse_posix("de_DE.ISO-8859-15@euro").unwrap();
+        assert_eq!(parsed.to_string(), "de-DE-u-cu-eur");
+        assert_eq!(parsed.codeset, Some("ISO-8859-15".to_string()));
+        assert_eq!(
+            parsed.extension().currency().unwrap().alphabetic_code,
+            "EUR"
+        );
+        assert!(LocaleIdentifier::parse_bcp47("en-US-x-private").is_none());
+        assert!(LocaleIdentifier::parse_bcp47("xx-YY").is_none());
+        assert!(LocaleIdentifier::parse_bcp47("en-999").is_none());
+        assert!(LocaleIdentifier::parse_android("values-en-US").is_none());
+        assert!(LocaleIdentifier::from_lcid(0x0C1F).is_none());
+    }
+
+    #[test]
+    fn test_format_dialects() {
+        let identifier = LocaleIdentifier::parse_bcp47("ca-ES-valencia").unwrap();
+        assert_eq!(identifier.to_posix().value, "ca_ES@valencia");
+        assert_eq!(identifier.to_icu().value, "ca_ES_VALENCIA");
+        assert_eq!(identifier.to_java().value, "ca_ES_valencia");
+        assert_eq!(identifier.to_android().value, "values-b+ca+ES+valencia");
+        assert_eq!(
+            convert_lossless("es-419", LocaleDialect::Bcp47, LocaleDialect::Java),
+            "es_419"
+        );
+        assert_eq!(
+            convert_lossless("es-419", LocaleDialect::Bcp47, LocaleDialect::WindowsLcid),
+            "0x580A"
+        );
+        assert_eq!(
+            convert_lossless("en-US", LocaleDialect::Bcp47, LocaleDialect::Android),
+            "values-en-rUS"
+        );
+        assert_eq!(
+            convert_lossless("sr__#Latn", LocaleDialect::Java, LocaleDialect::Bcp47),
+            "sr-Latn"
+        );
+        assert_eq!(
+            convert_lossless("th-TH-u-nu-thai", LocaleDialect::Bcp47, LocaleDialect::Java),
+            "th_TH_#u-nu-thai"
+        );
+        assert_eq!(
+            convert_lossless("de-CH-u-cu-chf", LocaleDialect::Bcp47, LocaleDialect::Icu),
+            "de_CH@currency=CHF"
+        );
+    }
+
+    #[test]
+    fn test_conversion_losses() {
+        assert_eq!(
+            convert_lossless("zh-Hant-TW", LocaleDialect::Bcp47, LocaleDialect::Posix),
+            "zh_TW"
+        );
+        let converted = convert("es-419", LocaleDialect::Bcp47, LocaleDialect::Posix).unwrap();
+        assert_eq!(converted.value, "es");
+        assert_eq!(
+            converted.losses,
+            vec![ConversionLoss::Region("419".to_string())]
+        );
+        let converted = convert(
+            "aa_ER.UTF-8@saaho",
+            LocaleDialect::Posix,
+            LocaleDialect::Bcp47,
+        )
+        .unwrap();
+        assert_eq!(converted.value, "aa-ER");
+        assert_eq!(
+            converted
+                .losses
+                .iter()
+                .map(|loss| loss.to_string())
+                .collect::<Vec<String>>(),
+            vec!["codeset UTF-8", "modifier saaho"]
+        );
+        let converted = convert(
+            "sr_Latn_RS@calendar=gregorian",
+            LocaleDialect::Icu,
+            LocaleDialect::Android,
+        )
+        .unwrap();
+        assert_eq!(converted.value, "values-b+sr+Latn+RS");
+        assert_eq!(
+            converted.losses,
+            vec![ConversionLoss::Keyword(
+                "ca".to_string(),
+                "gregory".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_lcid_conversions() {
+        let lcid = |tag: &str| LocaleIdentifier::parse_bcp47(tag).unwrap().to_lcid();
+        assert_eq!(lcid("en-US").unwrap().value, 0x0409);
+        assert_eq!(lcid("zh-Hant-TW").unwrap().value, 0x0404);
+        assert_eq!(lcid("zh-TW").unwrap().value, 0x0404);
+        assert_eq!(lcid("sr-RS").unwrap().value, 0x281A);
+        assert_eq!(lcid("es-ES").unwrap().value, 0x0C0A);
+        assert_eq!(lcid("es-ES-u-co-trad").unwrap().value, 0x040A);
+        let converted = lcid("en-NO").unwrap();
+        assert_eq!(converted.value, 0x0009);
+        assert_eq!(
+            converted.losses,
+            vec![ConversionLoss::Region("NO".to_string())]
+        );
+        let converted = lcid("zh-Hant-SG").unwrap();
+        assert_eq!(converted.value, 0x7C04);
+        assert_eq!(
+            converted.losses,
+            vec![ConversionLoss::Region("SG".to_string())]
+        );
+        assert!(lcid("tlh").is_none());
+        assert_eq!(
+            LocaleIdentifier::from_lcid(0x0001_0407)
+                .unwrap()
+                .to_string(),
+            "de-DE"
+        );
+        for (lcid, tag) in TAG_BY_LCID.iter() {
+            let identifier = LocaleIdentifier::parse_bcp47(tag)
+                .unwrap_or_else(|| panic!("invalid tag {} for {:#06X}", tag, lcid));
+            assert_eq!(identifier.to_lcid().unwrap().value, *lcid, "{}", tag);
+        }
+    }
+}
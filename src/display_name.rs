@@ -0,0 +1,642 @@
+/*!
+Localized display names for languages, scripts, countries, regions and
+currencies.
+
+The names held in each registry, such as `LanguageInfo::reference_name`
+and `CurrencyInfo::name`, are in English. This module provides the names
+used in other locales, so that Germany is `Deutschland` in German and
+`Allemagne` in French, along with the plural forms of currency names.
+
+Any entry that implements the [`DisplayName`](trait.DisplayName.html) trait
+can be named with [`display_name`](fn.display_name.html). Names are looked
+up along the [fallback chain](../fallback/index.html) of the requested
+locale, so that `fr-CA` uses the Canadian French name where there is one
+and the French name otherwise, then in English, and finally the English name
+from the registry is used. The chain includes the likely script of the
+locale, so that `zh-TW` uses the Traditional Chinese names of `zh-Hant`.
+
+```
+use locale_codes::{country, currency, display_name};
+use locale_codes::display_name::PluralCategory;
+
+let germany = country::lookup("DEU").unwrap();
+assert_eq!(display_name::display_name(germany, "de"), "Deutschland");
+assert_eq!(display_name::display_name(germany, "fr-CA"), "Allemagne");
+
+let dollar = currency::lookup_by_alpha("USD").unwrap();
+assert_eq!(display_name::display_name(dollar, "es"), "dólar estadounidense");
+assert_eq!(
+    display_name::currency_plural_name(dollar, PluralCategory::Other, "es"),
+    "dólares estadounidenses"
+);
+```
+
+A complete locale identifier can be named with
+[`locale_display_name`](fn.locale_display_name.html), which combines the
+names of the language, script, region and variants using the CLDR
+`localeDisplayPattern` of the viewer's locale. Options select short names,
+`UK` rather than `United Kingdom`, and dialect names, `British English`
+rather than `English (United Kingdom)`.
+
+```
+use locale_codes::display_name::{self, LocaleNameOptions, NameStyle};
+
+let options = LocaleNameOptions::default();
+assert_eq!(
+    display_name::locale_display_name("sr-Latn-RS", "fr", options).unwrap(),
+    "serbe (latin, Serbie)"
+);
+assert_eq!(
+    display_name::native_locale_display_name("sr-Latn-RS", options).unwrap(),
+    "srpski (latinica, Srbija)"
+);
+
+let options = LocaleNameOptions { style: NameStyle::Short, dialect_names: false };
+assert_eq!(
+    display_name::locale_display_name("en_GB", "en", options).unwrap(),
+    "English (UK)"
+);
+
+let options = LocaleNameOptions { style: NameStyle::Long, dialect_names: true };
+assert_eq!(
+    display_name::locale_display_name("en_GB", "en", options).unwrap(),
+    "British English"
+);
+```
+
+## Source - CLDR
+
+The data used here is taken from the language, dialect, script, territory,
+variant and currency names, and the locale display patterns, in the Unicode
+[CLDR](http://cldr.unicode.org/), for a selection of widely used locales.
+*/
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use serde::Deserialize;
+
+use crate::country::{self, CountryInfo};
+use crate::currency::CurrencyInfo;
+use crate::dialect::LocaleIdentifier;
+use crate::fallback::{self, ROOT};
+use crate::language::{self, LanguageInfo};
+use crate::region::{self, RegionInfo};
+use crate::script::{self, ScriptInfo};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// The kinds of entry that have display names.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NameKind {
+    /// A language, identified by its ISO-639 2-character code where one
+    /// exists, otherwise its 3-character code.
+    Language,
+    /// A script, identified by its ISO-15924 4-character code.
+    Script,
+    /// A country, identified by its ISO-3166 2-character code, or a region
+    /// identified by its 3-digit UN M.49 code.
+    Territory,
+    /// A currency, identified by its ISO-4217 alphabetic code.
+    Currency,
+}
+
+/// The CLDR plural categories, used to select the form of a currency name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PluralCategory {
+    /// The form for zero items, in some languages.
+    Zero,
+    /// The form for one item, and in some languages other singular numbers.
+    One,
+    /// The form for two items, in some languages.
+    Two,
+    /// The form for a few items, in some languages.
+    Few,
+    /// The form for many items, in some languages.
+    Many,
+    /// The general plural form, present in all languages.
+    Other,
+}
+
+/// The length of the names used by `locale_display_name`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NameStyle {
+    /// The standard names, `English (United Kingdom)`.
+    Long,
+    /// Short names, where CLDR has them, `English (UK)`.
+    Short,
+}
+
+/// Options for `locale_display_name`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LocaleNameOptions {
+    /// The length of the names used.
+    pub style: NameStyle,
+    /// Use the name of the dialect where CLDR has one, `British English`
+    /// rather than `English (United Kingdom)`.
+    pub dialect_names: bool,
+}
+
+/// Implemented by registry entries that have a display name.
+pub trait DisplayName {
+    /// Return the kind of name, and the code used to look it up.
+    fn name_key(&self) -> (NameKind, String);
+
+    /// Return the English name held in the registry.
+    fn english_name(&self) -> String;
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+/// The locale whose names are used when none is found in the requested
+/// locale.
+pub const FALLBACK_LOCALE: &str = "en";
+
+/// The pattern used to combine a language name with its qualifiers, where
+/// the locale does not define one.
+pub const DEFAULT_DISPLAY_PATTERN: &str = "{0} ({1})";
+
+/// The pattern used to separate the qualifiers of a language name, where
+/// the locale does not define one.
+pub const DEFAULT_DISPLAY_SEPARATOR: &str = "{0}, {1}";
+
+lazy_static! {
+    static ref DISPLAY_NAMES: HashMap<String, LocaleNames> = load_display_names_from_json();
+}
+
+/// Return the name of the entry in the given locale; where there is no name
+/// in that locale, or it is not valid, the English name is returned.
+pub fn display_name<T: DisplayName + ?Sized>(entry: &T, in_locale: &str) -> String {
+    debug!("display_name::display_name: {}", in_locale);
+    let (kind, code) = entry.name_key();
+    localized_name(kind, &code, in_locale)
+        .or_else(|| localized_name(kind, &code, FALLBACK_LOCALE))
+        .unwrap_or_else(|| entry.english_name())
+}
+
+/// Return the name for the code in the given locale, or in the locales it
+/// falls back to, `None` if there is no name or the locale is not valid.
+/// No English fallback is used, unless the locale is itself English.
+pub fn localized_name(kind: NameKind, code: &str, in_locale: &str) -> Option<String> {
+    find_name(in_locale, |names| {
+        match kind {
+            NameKind::Language => &names.languages,
+            NameKind::Script => &names.scripts,
+            NameKind::Territory => &names.territories,
+            NameKind::Currency => &names.currencies,
+        }
+        .get(code)
+    })
+}
+
+/// Return the name of a complete locale, such as `sr-Latn-RS` or `en_GB`,
+/// in the given locale; `None` if the locale to be named is not valid.
+/// The language name is followed by the names of the script, region and
+/// variants, using the display pattern of the locale the name is in.
+pub fn locale_display_name(
+    locale: &str,
+    in_locale: &str,
+    options: LocaleNameOptions,
+) -> Option<String> {
+    debug!(
+        "display_name::locale_display_name: {} {} {:?}",
+        locale, in_locale, options
+    );
+    let identifier = LocaleIdentifier::parse_bcp47(&locale.replace('_', "-"))?;
+    let short = options.style == NameStyle::Short;
+    let mut script = identifier.script.as_deref();
+    let mut region = identifier.region.as_deref();
+
+    let mut name = None;
+    if options.dialect_names {
+        // the longest dialect name wins, and its subtags are not repeated
+        // as qualifiers.
+        for (dialect_script, dialect_region) in [(script, region), (script, None), (None, region)] {
+            if dialect_script.is_none() && dialect_region.is_none() {
+                continue;
+            }
+            let mut dialect = identifier.language.clone();
+            for subtag in [dialect_script, dialect_region].iter().flatten() {
+                dialect.push_str(&format!("-{}", subtag));
+            }
+            let found = short_or_long(in_locale, short, |names| {
+                (
+                    names.short_dialects.get(&dialect),
+                    names.dialects.get(&dialect),
+                )
+            });
+            if found.is_some() {
+                name = found;
+                if dialect_script.is_some() {
+                    script = None;
+                }
+                if dialect_region.is_some() {
+                    region = None;
+                }
+                break;
+            }
+        }
+    }
+    let name = name.unwrap_or_else(|| {
+        short_or_long(in_locale, short, |names| {
+            (
+                names.short_languages.get(&identifier.language),
+                names.languages.get(&identifier.language),
+            )
+        })
+        .or_else(|| language::lookup(&identifier.language).map(|language| language.english_name()))
+        .unwrap_or_else(|| identifier.language.clone())
+    });
+
+    let mut qualifiers: Vec<String> = Vec::new();
+    if let Some(script) = script {
+        qualifiers.push(
+            find_name_or_english(in_locale, |names| names.scripts.get(script))
+                .or_else(|| script::lookup_by_alpha(script).map(|info| info.english_name()))
+                .unwrap_or_else(|| script.to_string()),
+        );
+    }
+    if let Some(region) = region {
+        qualifiers.push(
+            short_or_long(in_locale, short, |names| {
+                (
+                    names.short_territories.get(region),
+                    names.territories.get(region),
+                )
+            })
+            .or_else(|| region_english_name(region))
+            .unwrap_or_else(|| region.to_string()),
+        );
+    }
+    for variant in &identifier.variants {
+        qualifiers.push(
+            find_name_or_english(in_locale, |names| names.variants.get(variant))
+                .unwrap_or_else(|| variant.to_ascii_uppercase()),
+        );
+    }
+
+    if qualifiers.is_empty() {
+        return Some(name);
+    }
+    let pattern = find_name_or_english(in_locale, |names| names.patterns.get("pattern"))
+        .unwrap_or_else(|| DEFAULT_DISPLAY_PATTERN.to_string());
+    let separator = find_name_or_english(in_locale, |names| names.patterns.get("separator"))
+        .unwrap_or_else(|| DEFAULT_DISPLAY_SEPARATOR.to_string());
+    let qualifiers = qualifiers
+        .into_iter()
+        .reduce(|joined, qualifier| apply_pattern(&separator, &joined, &qualifier))
+        .unwrap_or_default();
+    Some(apply_pattern(&pattern, &name, &qualifiers))
+}
+
+/// Return the name of a complete locale in its own language, as shown in a
+/// language switcher; `None` if the locale is not valid.
+pub fn native_locale_display_name(locale: &str, options: LocaleNameOptions) -> Option<String> {
+    locale_display_name(locale, &locale.replace('_', "-"), options)
+}
+
+/// Return the plural form of the currency's name, for the given plural
+/// category, in the given locale. Where the locale has no form for the
+/// category the `Other` form is used, and where it has no plural forms at
+/// all the result is the same as `display_name`.
+pub fn currency_plural_name(
+    currency: &CurrencyInfo,
+    count: PluralCategory,
+    in_locale: &str,
+) -> String {
+    debug!(
+        "display_name::currency_plural_name: {} {}",
+        currency.alphabetic_code, count
+    );
+    let plural_name = |locale: &str| -> Option<String> {
+        locale_chain(locale).iter().find_map(|names| {
+            let forms = names.currency_plurals.get(&currency.alphabetic_code)?;
+            forms
+                .get(&count.to_string())
+                .or_else(|| forms.get(&PluralCategory::Other.to_string()))
+                .cloned()
+        })
+    };
+    plural_name(in_locale)
+        .or_else(|| localized_name(NameKind::Currency, &currency.alphabetic_code, in_locale))
+        .or_else(|| plural_name(FALLBACK_LOCALE))
+        .unwrap_or_else(|| currency.english_name())
+}
+
+/// Return the locales for which display names are available.
+pub fn available_locales() -> Vec<String> {
+    DISPLAY_NAMES.keys().cloned().collect()
+}
+
+/// Return the names of the given kind defined directly in the locale, with
+/// no fallback, `None` if there is no data for the locale.
+pub(crate) fn names_in_locale(
+    kind: NameKind,
+    locale: &str,
+) -> Option<&'static HashMap<String, String>> {
+    DISPLAY_NAMES.get(locale).map(|names| match kind {
+        NameKind::Language => &names.languages,
+        NameKind::Script => &names.scripts,
+        NameKind::Territory => &names.territories,
+        NameKind::Currency => &names.currencies,
+    })
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for PluralCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "{}",
+            match self {
+                PluralCategory::Zero => "zero",
+                PluralCategory::One => "one",
+                PluralCategory::Two => "two",
+                PluralCategory::Few => "few",
+                PluralCategory::Many => "many",
+                PluralCategory::Other => "other",
+            }
+        )
+    }
+}
+
+impl Default for LocaleNameOptions {
+    fn default() -> Self {
+        LocaleNameOptions {
+            style: NameStyle::Long,
+            dialect_names: false,
+        }
+    }
+}
+
+impl DisplayName for LanguageInfo {
+    fn name_key(&self) -> (NameKind, String) {
+        (
+            NameKind::Language,
+            self.short_code.as_ref().unwrap_or(&self.code).to_string(),
+        )
+    }
+
+    fn english_name(&self) -> String {
+        self.reference_name.clone()
+    }
+}
+
+impl DisplayName for ScriptInfo {
+    fn name_key(&self) -> (NameKind, String) {
+        (NameKind::Script, self.alphabetic_code.clone())
+    }
+
+    fn english_name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+impl DisplayName for CountryInfo {
+    fn name_key(&self) -> (NameKind, String) {
+        (NameKind::Territory, self.short_code.clone())
+    }
+
+    fn english_name(&self) -> String {
+        match region::lookup(self.country_code) {
+            Some(region) => region.name.clone(),
+            None => self.short_code.clone(),
+        }
+    }
+}
+
+impl DisplayName for RegionInfo {
+    fn name_key(&self) -> (NameKind, String) {
+        // a region that is also a country is named by its country code.
+        match country::lookup_by_numeric(self.code) {
+            Some(country) => country.name_key(),
+            None => (NameKind::Territory, format!("{:03}", self.code)),
+        }
+    }
+
+    fn english_name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+impl DisplayName for CurrencyInfo {
+    fn name_key(&self) -> (NameKind, String) {
+        (NameKind::Currency, self.alphabetic_code.clone())
+    }
+
+    fn english_name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+struct LocaleNames {
+    languages: HashMap<String, String>,
+    short_languages: HashMap<String, String>,
+    dialects: HashMap<String, String>,
+    short_dialects: HashMap<String, String>,
+    scripts: HashMap<String, String>,
+    territories: HashMap<String, String>,
+    short_territories: HashMap<String, String>,
+    variants: HashMap<String, String>,
+    currencies: HashMap<String, String>,
+    currency_plurals: HashMap<String, HashMap<String, String>>,
+    patterns: HashMap<String, String>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn locale_chain(locale: &str) -> Vec<&'static LocaleNames> {
+    fallback::fallback_chain(locale)
+        .unwrap_or_default()
+        .iter()
+        .filter(|locale| *locale != ROOT)
+        .filter_map(|locale| DISPLAY_NAMES.get(locale))
+        .collect()
+}
+
+fn find_name<F>(in_locale: &str, select: F) -> Option<String>
+where
+    F: Fn(&LocaleNames) -> Option<&String>,
+{
+    locale_chain(in_locale)
+        .iter()
+        .find_map(|names| select(names))
+        .cloned()
+}
+
+fn find_name_or_english<F>(in_locale: &str, select: F) -> Option<String>
+where
+    F: Fn(&LocaleNames) -> Option<&String>,
+{
+    find_name(in_locale, &select).or_else(|| find_name(FALLBACK_LOCALE, &select))
+}
+
+fn short_or_long<F>(in_locale: &str, short: bool, select: F) -> Option<String>
+where
+    F: Fn(&LocaleNames) -> (Option<&String>, Option<&String>),
+{
+    // a short name is only preferred over the long name in the same locale.
+    find_name_or_english(in_locale, |names| {
+        let (short_name, long_name) = select(names);
+        if short {
+            short_name.or(long_name)
+        } else {
+            long_name
+        }
+    })
+}
+
+fn region_english_name(region: &str) -> Option<String> {
+    if region.len() == 3 {
+        region::lookup(region.parse::<u16>().ok()?).map(|info| info.english_name())
+    } else {
+        country::lookup(region).map(|info| info.english_name())
+    }
+}
+
+fn apply_pattern(pattern: &str, first: &str, second: &str) -> String {
+    pattern.replace("{0}", first).replace("{1}", second)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Generated Data
+// ------------------------------------------------------------------------------------------------
+
+fn load_display_names_from_json() -> HashMap<String, LocaleNames> {
+    info!("load_display_names_from_json - loading JSON");
+    let raw_data = include_bytes!("data/display_names.json");
+    let name_map: HashMap<String, LocaleNames> = serde_json::from_slice(raw_data).unwrap();
+    info!(
+        "load_display_names_from_json - loaded {} locales",
+        name_map.len()
+    );
+    name_map
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::currency;
+
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn test_display_names() {
+        let germany = country::lookup("DEU").unwrap();
+        assert_eq!(display_name(germany, "de"), "Deutschland");
+        assert_eq!(display_name(germany, "de_AT"), "Deutschland");
+        assert_eq!(display_name(germany, "fr"), "Allemagne");
+        assert_eq!(display_name(germany, "ja"), "ドイツ");
+        assert_eq!(display_name(germany, "nb"), "Tyskland");
+        assert_eq!(display_name(germany, "no"), "Tyskland");
+        assert_eq!(display_name(germany, "zh"), "德国");
+        assert_eq!(display_name(germany, "zh-Hant"), "德國");
+        assert_eq!(display_name(germany, "zh-TW"), "德國");
+        assert_eq!(display_name(germany, "tlh"), "Germany");
+        assert_eq!(display_name(germany, "not a locale"), "Germany");
+
+        let german = language::lookup("deu").unwrap();
+        assert_eq!(display_name(german, "fr"), "allemand");
+        let cyrillic = script::lookup_by_alpha("Cyrl").unwrap();
+        assert_eq!(display_name(cyrillic, "ru"), "кириллица");
+        let latin_america = region::lookup(419).unwrap();
+        assert_eq!(display_name(latin_america, "es"), "Latinoamérica");
+        let afghanistan = region::lookup(4).unwrap();
+        assert_eq!(display_name(afghanistan, "de"), "Afghanistan");
+
+        // a language without a CLDR name falls back to the registry.
+        let ghotuo = language::lookup("aaa").unwrap();
+        assert!(localized_name(NameKind::Language, "aaa", "en").is_none());
+        assert_eq!(display_name(ghotuo, "fr"), ghotuo.reference_name);
+    }
+
+    #[test]
+    fn test_locale_display_names() {
+        let long = LocaleNameOptions::default();
+        let short = LocaleNameOptions {
+            style: NameStyle::Short,
+            dialect_names: false,
+        };
+        let dialect = LocaleNameOptions {
+            style: NameStyle::Long,
+            dialect_names: true,
+        };
+        let short_dialect = LocaleNameOptions {
+            style: NameStyle::Short,
+            dialect_names: true,
+        };
+        let name =
+            |locale, in_locale, options| locale_display_name(locale, in_locale, options).unwrap();
+
+        assert_eq!(name("en", "en", long), "English");
+        assert_eq!(name("en-GB", "en", long), "English (United Kingdom)");
+        assert_eq!(name("en_GB", "en", short), "English (UK)");
+        assert_eq!(name("en-GB", "en", dialect), "British English");
+        assert_eq!(name("en-GB", "en", short_dialect), "UK English");
+        assert_eq!(name("en-GB", "fr", dialect), "anglais britannique");
+        assert_eq!(name("sr-Latn-RS", "fr", long), "serbe (latin, Serbie)");
+        assert_eq!(
+            name("zh-Hant-TW", "en", dialect),
+            "Traditional Chinese (Taiwan)"
+        );
+        assert_eq!(name("de-CH", "zh", long), "德语（瑞士）");
+        assert_eq!(name("es-419", "en", long), "Spanish (Latin America)");
+        assert_eq!(
+            name("ca-ES-valencia", "fr", long),
+            "catalan (Espagne, valencien)"
+        );
+        assert_eq!(
+            native_locale_display_name("sr-Latn-RS", long).unwrap(),
+            "srpski (latinica, Srbija)"
+        );
+        assert_eq!(
+            native_locale_display_name("zh-TW", long).unwrap(),
+            "中文（台灣）"
+        );
+
+        // names missing from the viewer's locale fall back to English, then
+        // to the registry.
+        assert_eq!(name("fr-CA", "tlh", long), "French (Canada)");
+        assert!(locale_display_name("not a locale", "en", long).is_none());
+    }
+
+    #[test]
+    fn test_currency_names() {
+        let dollar = currency::lookup_by_alpha("USD").unwrap();
+        assert_eq!(display_name(dollar, "es-MX"), "dólar estadounidense");
+        assert_eq!(
+            currency_plural_name(dollar, PluralCategory::One, "es"),
+            "dólar estadounidense"
+        );
+        assert_eq!(
+            currency_plural_name(dollar, PluralCategory::Few, "es"),
+            "dólares estadounidenses"
+        );
+        assert_eq!(
+            currency_plural_name(dollar, PluralCategory::Few, "ru"),
+            "доллара США"
+        );
+        assert_eq!(
+            currency_plural_name(dollar, PluralCategory::Other, "tlh"),
+            "US dollars"
+        );
+        assert!(available_locales().contains(&"zh-Hant".to_string()));
+    }
+}
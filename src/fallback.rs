@@ -0,0 +1,229 @@
+/*!
+Locale fallback chains, for looking up localized resources.
+
+Where the resources for a locale are missing, a resource bundle, or message,
+loader falls back to those of a more general locale. Usually this is done by
+removing the last subtag, `zh-Hans-SG` falls back to `zh-Hans`, then to `zh`
+and finally to the root locale. However, some locales have an explicit
+parent in the Unicode CLDR; Portuguese as spoken in Angola, `pt-AO`, is
+closer to the European `pt-PT` than to `pt`, which is Brazilian Portuguese,
+Mexican Spanish, `es-MX`, falls back to Latin American Spanish, `es-419`, and
+Traditional Chinese, `zh-Hant`, falls back directly to the root locale rather
+than to `zh`, which is Simplified Chinese.
+
+Where a locale has a region but no script, and the likely script for that
+region differs from the usual script of the language, the script is added
+from the [`likely_subtags`](../likely_subtags/index.html) data before
+falling back; so `zh-TW` falls back through `zh-Hant-TW` and `zh-Hant`.
+
+Locales are identified as `language[-script][-region]` where each subtag is
+validated against the [`language`](../language/index.html),
+[`script`](../script/index.html) and [`country`](../country/index.html)
+registries; the region may also be one of the UN M.49 numeric codes in the
+[`region`](../region/index.html) registry, such as `419` for Latin America.
+
+```
+use locale_codes::fallback;
+
+let chain = fallback::fallback_chain("pt-AO").unwrap();
+assert_eq!(chain, vec!["pt-AO", "pt-PT", "pt", "root"]);
+
+let chain = fallback::fallback_chain("zh-TW").unwrap();
+assert_eq!(chain, vec!["zh-TW", "zh-Hant-TW", "zh-Hant", "root"]);
+
+let shipped = ["en", "pt", "pt-PT"];
+let bundle = fallback::resolve("pt_MZ", |locale| shipped.contains(&locale));
+assert_eq!(bundle, Some("pt-PT".to_string()));
+```
+
+## Source - CLDR
+
+The data used here is taken from the `parentLocales` data in the Unicode
+[CLDR](http://cldr.unicode.org/) supplemental data.
+*/
+
+use std::collections::HashMap;
+
+use crate::likely_subtags::{LocaleSubtags, UNDETERMINED};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// The identifier of the root locale, the last in every fallback chain.
+pub const ROOT: &str = "root";
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+lazy_static! {
+    static ref PARENT_LOCALES: HashMap<String, String> = load_parent_locales_from_json();
+}
+
+/// Return the parent of the given locale, `None` if the locale is the root
+/// locale or is not valid.
+pub fn parent(locale: &str) -> Option<String> {
+    debug!("fallback::parent: {}", locale);
+    let locale = normalize(locale)?;
+    if locale == ROOT {
+        None
+    } else {
+        Some(with_likely_script(&locale).unwrap_or_else(|| parent_of(&locale)))
+    }
+}
+
+/// Return the fallback chain for the given locale, starting with the locale
+/// itself, in a normalized form, and ending with the root locale. Returns
+/// `None` if the locale is not valid.
+pub fn fallback_chain(locale: &str) -> Option<Vec<String>> {
+    debug!("fallback::fallback_chain: {}", locale);
+    let mut current = normalize(locale)?;
+    let mut chain = Vec::new();
+    if let Some(scripted) = with_likely_script(&current) {
+        chain.push(current);
+        current = scripted;
+    }
+    while current != ROOT {
+        let next = parent_of(&current);
+        chain.push(current);
+        current = next;
+    }
+    chain.push(current);
+    Some(chain)
+}
+
+/// Return the first locale in the fallback chain for the given locale for
+/// which the `is_available` predicate returns `true`, `None` if there is no
+/// such locale, or if the locale is not valid.
+pub fn resolve<F>(locale: &str, is_available: F) -> Option<String>
+where
+    F: Fn(&str) -> bool,
+{
+    fallback_chain(locale)?
+        .into_iter()
+        .find(|candidate| is_available(candidate))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn normalize(locale: &str) -> Option<String> {
+    if locale == ROOT || locale == UNDETERMINED {
+        return Some(ROOT.to_string());
+    }
+    LocaleSubtags::parse(locale).map(|subtags| subtags.to_string())
+}
+
+// a locale with a region, but no script, has the likely script added where
+// it differs from the usual script of the language; `zh-TW` is written in
+// Traditional Chinese, `zh-Hant-TW`, although `zh` is written in Simplified.
+fn with_likely_script(locale: &str) -> Option<String> {
+    let subtags = LocaleSubtags::parse(locale)?;
+    if subtags.script.is_some() || subtags.country.is_none() {
+        return None;
+    }
+    let likely_script = subtags.maximize().script;
+    let usual_script = LocaleSubtags::new(&subtags.language, None, None)?
+        .maximize()
+        .script;
+    if likely_script.is_some() && likely_script != usual_script {
+        LocaleSubtags::new(
+            &subtags.language,
+            likely_script.as_deref(),
+            subtags.country.as_deref(),
+        )
+        .map(|subtags| subtags.to_string())
+    } else {
+        None
+    }
+}
+
+fn parent_of(locale: &str) -> String {
+    match PARENT_LOCALES.get(locale) {
+        Some(parent) => parent.to_string(),
+        None => match locale.rfind('-') {
+            Some(index) => locale[..index].to_string(),
+            None => ROOT.to_string(),
+        },
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Generated Data
+// ------------------------------------------------------------------------------------------------
+
+fn load_parent_locales_from_json() -> HashMap<String, String> {
+    info!("load_parent_locales_from_json - loading JSON");
+    let raw_data = include_bytes!("data/parent_locales.json");
+    let parent_map: HashMap<String, String> = serde_json::from_slice(raw_data).unwrap();
+    info!(
+        "load_parent_locales_from_json - loaded {} locales",
+        parent_map.len()
+    );
+    parent_map
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn test_fallback_chain() {
+        let chain = |locale: &str| -> Vec<String> { fallback_chain(locale).unwrap() };
+        assert_eq!(
+            chain("zh-Hans-SG"),
+            vec!["zh-Hans-SG", "zh-Hans", "zh", "root"]
+        );
+        assert_eq!(chain("sr-Latn-ME"), vec!["sr-Latn-ME", "sr-Latn", "root"]);
+        assert_eq!(chain("sr_latn_me"), vec!["sr-Latn-ME", "sr-Latn", "root"]);
+        assert_eq!(
+            chain("sr-ME"),
+            vec!["sr-ME", "sr-Latn-ME", "sr-Latn", "root"]
+        );
+        assert_eq!(chain("sr-RS"), vec!["sr-RS", "sr", "root"]);
+        assert_eq!(chain("pt-AO"), vec!["pt-AO", "pt-PT", "pt", "root"]);
+        assert_eq!(chain("en-AU"), vec!["en-AU", "en-001", "en", "root"]);
+        assert_eq!(chain("es-MX"), vec!["es-MX", "es-419", "es", "root"]);
+        assert_eq!(chain("es-419"), vec!["es-419", "es", "root"]);
+        assert_eq!(
+            chain("zh-Hant-MO"),
+            vec!["zh-Hant-MO", "zh-Hant-HK", "zh-Hant", "root"]
+        );
+        assert_eq!(
+            chain("zh-TW"),
+            vec!["zh-TW", "zh-Hant-TW", "zh-Hant", "root"]
+        );
+        assert_eq!(
+            chain("zh-HK"),
+            vec!["zh-HK", "zh-Hant-HK", "zh-Hant", "root"]
+        );
+        assert_eq!(chain("zh-CN"), vec!["zh-CN", "zh", "root"]);
+        assert_eq!(chain("en-US"), vec!["en-US", "en", "root"]);
+        assert_eq!(chain("root"), vec!["root"]);
+        assert!(fallback_chain("xx-YY").is_none());
+        assert!(fallback_chain("en-999").is_none());
+        assert!(fallback_chain("en-GB-419").is_none());
+    }
+
+    #[test]
+    fn test_parent_and_resolve() {
+        assert_eq!(parent("nn"), Some("no".to_string()));
+        assert_eq!(parent("fr"), Some("root".to_string()));
+        assert_eq!(parent("zh-Hant"), Some("root".to_string()));
+        assert_eq!(parent("zh-TW"), Some("zh-Hant-TW".to_string()));
+        assert_eq!(parent("root"), None);
+        let shipped = ["en", "en-001", "fr"];
+        let available = |locale: &str| shipped.contains(&locale);
+        assert_eq!(resolve("en-NZ", available), Some("en-001".to_string()));
+        assert_eq!(resolve("en-US", available), Some("en".to_string()));
+        assert_eq!(resolve("de-AT", available), None);
+        assert_eq!(resolve("de-AT", |_| true), Some("de-AT".to_string()));
+    }
+}
@@ -0,0 +1,847 @@
+/*!
+Locale data from the GNU C Library, as installed on Linux systems.
+
+The GNU C Library (glibc) describes the locales it can build in the file
+`/usr/share/i18n/SUPPORTED`, maps old-style locale names such as `german` to
+locale names in `/usr/share/locale/locale.alias`, and defines each locale in
+a source file under `/usr/share/i18n/locales`. These files are read at
+runtime, as they reflect the local system rather than the data embedded in
+this crate.
+
+* [`parse_supported`](fn.parse_supported.html) reads the `SUPPORTED` file
+  into `SupportedLocale` values, each with a locale name and the character
+  map (codeset) it is built with.
+* [`parse_locale_aliases`](fn.parse_locale_aliases.html) reads the
+  `locale.alias` file into `LocaleAlias` values.
+* [`LocaleDefinition`](struct.LocaleDefinition.html) reads a locale source
+  file, keeping the `LC_MONETARY`, `LC_NUMERIC`, `LC_TIME`, `LC_ADDRESS` and
+  `LC_TELEPHONE` categories. Where a category is copied from another locale,
+  using the `copy` keyword, loading the file will also load the copied
+  category.
+
+The codes in each are cross-checked against the
+[`language`](../language/index.html), [`country`](../country/index.html),
+[`currency`](../currency/index.html), [`telephone`](../telephone/index.html)
+and [`codeset`](../codeset/index.html) registries.
+
+```
+use locale_codes::glibc::LocaleDefinition;
+
+let definition = LocaleDefinition::parse(r#"
+comment_char %
+escape_char /
+LC_MONETARY
+int_curr_symbol     "CHF "
+currency_symbol     "CHF"
+mon_decimal_point   "."
+mon_thousands_sep   "<U2019>"
+mon_grouping        3;3
+END LC_MONETARY
+"#).unwrap();
+
+let monetary = definition.monetary.unwrap();
+assert_eq!(monetary.currency().unwrap().name, "Swiss Franc");
+assert_eq!(monetary.mon_thousands_sep, "\u{2019}");
+assert_eq!(monetary.mon_grouping, vec![3, 3]);
+```
+*/
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fs;
+use std::io::{Error, ErrorKind, Result};
+use std::path::Path;
+use std::str::Chars;
+
+use crate::codeset::{self, CodesetInfo};
+use crate::country::{self, CountryInfo};
+use crate::currency::{self, CurrencyInfo};
+use crate::language::{self, LanguageInfo};
+use crate::posix::{LocaleCategory, PosixLocale};
+use crate::telephone;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// The directory in which the glibc locale sources are commonly installed.
+pub const SYSTEM_I18N_DIR: &str = "/usr/share/i18n";
+
+/// The location of the glibc locale alias file.
+pub const SYSTEM_LOCALE_ALIAS_FILE: &str = "/usr/share/locale/locale.alias";
+
+/// A locale listed in the glibc `SUPPORTED` file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SupportedLocale {
+    /// The locale name, for example `de_DE.UTF-8` or `aa_ER@saaho`.
+    pub name: String,
+    /// The name of the character map, or codeset, used to build the locale.
+    pub charmap: String,
+}
+
+/// An entry in the glibc `locale.alias` file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocaleAlias {
+    /// The alias, for example `german`.
+    pub alias: String,
+    /// The locale name the alias refers to, for example `de_DE.ISO-8859-1`.
+    pub locale: String,
+}
+
+/// The `LC_MONETARY` category of a locale definition.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MonetaryFormat {
+    /// The international currency symbol, the ISO-4217 code followed by a
+    /// separator character, commonly a space.
+    pub int_curr_symbol: String,
+    /// The local currency symbol.
+    pub currency_symbol: String,
+    /// The decimal point for monetary values.
+    pub mon_decimal_point: String,
+    /// The thousands separator for monetary values.
+    pub mon_thousands_sep: String,
+    /// The size of each group of digits, from the right; `-1` ends grouping.
+    pub mon_grouping: Vec<i32>,
+    /// The sign for non-negative monetary values.
+    pub positive_sign: String,
+    /// The sign for negative monetary values.
+    pub negative_sign: String,
+    /// The number of fractional digits in internationally formatted values.
+    pub int_frac_digits: Option<i32>,
+    /// The number of fractional digits in locally formatted values.
+    pub frac_digits: Option<i32>,
+    /// `true` if the currency symbol precedes a non-negative value.
+    pub p_cs_precedes: Option<bool>,
+    /// `true` if the currency symbol precedes a negative value.
+    pub n_cs_precedes: Option<bool>,
+}
+
+/// The `LC_NUMERIC` category of a locale definition.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NumericFormat {
+    /// The decimal point.
+    pub decimal_point: String,
+    /// The thousands separator.
+    pub thousands_sep: String,
+    /// The size of each group of digits, from the right; `-1` ends grouping.
+    pub grouping: Vec<i32>,
+}
+
+/// The `LC_TIME` category of a locale definition.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TimeFormat {
+    /// The abbreviated day names, starting with Sunday.
+    pub abday: Vec<String>,
+    /// The full day names, starting with Sunday.
+    pub day: Vec<String>,
+    /// The abbreviated month names, starting with January.
+    pub abmon: Vec<String>,
+    /// The full month names, starting with January.
+    pub mon: Vec<String>,
+    /// The ante-meridiem and post-meridiem strings, empty for a 24-hour
+    /// clock.
+    pub am_pm: Vec<String>,
+    /// The `strftime` format for the date and time, `%c`.
+    pub d_t_fmt: String,
+    /// The `strftime` format for the date, `%x`.
+    pub d_fmt: String,
+    /// The `strftime` format for the time, `%X`.
+    pub t_fmt: String,
+    /// The `strftime` format for the time with an AM/PM indicator, `%r`.
+    pub t_fmt_ampm: String,
+    /// The first day of the week, `1` for the first of `day`.
+    pub first_weekday: Option<i32>,
+}
+
+/// The `LC_ADDRESS` category of a locale definition.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AddressFormat {
+    /// The format of a postal address, using glibc field descriptors.
+    pub postal_fmt: String,
+    /// The name of the country, in the language of the locale.
+    pub country_name: String,
+    /// The ISO-3166 2-character identifier of the country.
+    pub country_ab2: Option<String>,
+    /// The ISO-3166 3-character identifier of the country.
+    pub country_ab3: Option<String>,
+    /// The ISO-3166 numeric identifier of the country.
+    pub country_num: Option<u16>,
+    /// The name of the language, in the language of the locale.
+    pub lang_name: String,
+    /// The ISO-639 2-character identifier of the language.
+    pub lang_ab: Option<String>,
+    /// The ISO-639 3-character (terminology) identifier of the language.
+    pub lang_term: Option<String>,
+}
+
+/// The `LC_TELEPHONE` category of a locale definition.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TelephoneFormat {
+    /// The format of an international telephone number.
+    pub tel_int_fmt: String,
+    /// The format of a domestic telephone number.
+    pub tel_dom_fmt: String,
+    /// The prefix used to dial an international number.
+    pub int_select: String,
+    /// The country calling code.
+    pub int_prefix: String,
+}
+
+/// A glibc locale definition source file; only the categories listed are
+/// kept.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LocaleDefinition {
+    /// The `LC_MONETARY` category.
+    pub monetary: Option<MonetaryFormat>,
+    /// The `LC_NUMERIC` category.
+    pub numeric: Option<NumericFormat>,
+    /// The `LC_TIME` category.
+    pub time: Option<TimeFormat>,
+    /// The `LC_ADDRESS` category.
+    pub address: Option<AddressFormat>,
+    /// The `LC_TELEPHONE` category.
+    pub telephone: Option<TelephoneFormat>,
+    /// The categories copied from other locales, and the name of the locale
+    /// each was copied from.
+    pub copies: HashMap<LocaleCategory, String>,
+}
+
+/// A value in a locale definition that is either not present in the
+/// corresponding registry, or is inconsistent with other values.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DefinitionIssue {
+    /// The category containing the value.
+    pub category: LocaleCategory,
+    /// The keyword of the value.
+    pub keyword: String,
+    /// The value, as written.
+    pub value: String,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+/// Parse the content of a glibc `SUPPORTED` file.
+pub fn parse_supported(content: &str) -> Result<Vec<SupportedLocale>> {
+    let mut supported: Vec<SupportedLocale> = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.trim_end_matches('\\').trim_end();
+        match line.split_whitespace().collect::<Vec<&str>>().as_slice() {
+            [name, charmap] => supported.push(SupportedLocale {
+                name: name.to_string(),
+                charmap: charmap.to_string(),
+            }),
+            _ => return Err(invalid_data(line)),
+        }
+    }
+    Ok(supported)
+}
+
+/// Load a glibc `SUPPORTED` file.
+pub fn load_supported<P: AsRef<Path>>(path: P) -> Result<Vec<SupportedLocale>> {
+    info!("glibc::load_supported - loading {:?}", path.as_ref());
+    parse_supported(&fs::read_to_string(path)?)
+}
+
+/// Load the system's glibc `SUPPORTED` file.
+pub fn load_system_supported() -> Result<Vec<SupportedLocale>> {
+    load_supported(Path::new(SYSTEM_I18N_DIR).join("SUPPORTED"))
+}
+
+/// Parse the content of a glibc `locale.alias` file.
+pub fn parse_locale_aliases(content: &str) -> Result<Vec<LocaleAlias>> {
+    let mut aliases: Vec<LocaleAlias> = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match line.split_whitespace().collect::<Vec<&str>>().as_slice() {
+            [alias, locale] => aliases.push(LocaleAlias {
+                alias: alias.to_string(),
+                locale: locale.to_string(),
+            }),
+            _ => return Err(invalid_data(line)),
+        }
+    }
+    Ok(aliases)
+}
+
+/// Load a glibc `locale.alias` file.
+pub fn load_locale_aliases<P: AsRef<Path>>(path: P) -> Result<Vec<LocaleAlias>> {
+    info!("glibc::load_locale_aliases - loading {:?}", path.as_ref());
+    parse_locale_aliases(&fs::read_to_string(path)?)
+}
+
+/// Load the system's glibc `locale.alias` file.
+pub fn load_system_locale_aliases() -> Result<Vec<LocaleAlias>> {
+    load_locale_aliases(SYSTEM_LOCALE_ALIAS_FILE)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl SupportedLocale {
+    /// Return the parsed locale name, `None` if it is not valid.
+    pub fn locale(&self) -> Option<PosixLocale> {
+        PosixLocale::parse(&self.name)
+    }
+
+    /// Return the `CodesetInfo` for the character map.
+    pub fn codeset(&self) -> Option<&'static CodesetInfo> {
+        codeset::lookup_by_alias(&self.charmap)
+    }
+}
+
+impl LocaleAlias {
+    /// Return the parsed locale name the alias refers to, `None` if it is
+    /// not valid.
+    pub fn locale(&self) -> Option<PosixLocale> {
+        PosixLocale::parse(&self.locale)
+    }
+}
+
+impl MonetaryFormat {
+    /// Return the `CurrencyInfo` for the international currency symbol.
+    pub fn currency(&self) -> Option<&'static CurrencyInfo> {
+        let code = self.int_curr_symbol.trim();
+        if code.len() == 3 {
+            currency::lookup_by_alpha(code)
+        } else {
+            None
+        }
+    }
+}
+
+impl AddressFormat {
+    /// Return the `CountryInfo` for the country, from the 2-character
+    /// identifier if present, otherwise the 3-character identifier, or
+    /// otherwise the numeric identifier.
+    pub fn country(&self) -> Option<&'static CountryInfo> {
+        match self.country_ab2.as_ref().or(self.country_ab3.as_ref()) {
+            Some(code) => country::lookup(code),
+            None => self.country_num.and_then(country::lookup_by_numeric),
+        }
+    }
+
+    /// Return the `LanguageInfo` for the language, from the 3-character
+    /// identifier if present, otherwise the 2-character identifier.
+    pub fn language(&self) -> Option<&'static LanguageInfo> {
+        self.lang_term
+            .as_ref()
+            .or(self.lang_ab.as_ref())
+            .and_then(|code| language::lookup(code))
+    }
+}
+
+impl LocaleDefinition {
+    /// Parse the content of a glibc locale definition file. Categories that
+    /// are copied from another locale are recorded in `copies`, but are
+    /// not loaded.
+    pub fn parse(content: &str) -> Result<Self> {
+        let sections = parse_sections(content)?;
+        let mut definition = LocaleDefinition::default();
+        for (category, section) in sections {
+            if let Some(source) = section.copy {
+                definition.copies.insert(category, source);
+                continue;
+            }
+            match category {
+                LocaleCategory::Monetary => definition.monetary = Some(section.monetary()),
+                LocaleCategory::Numeric => definition.numeric = Some(section.numeric()),
+                LocaleCategory::Time => definition.time = Some(section.time()),
+                LocaleCategory::Address => definition.address = Some(section.address()),
+                LocaleCategory::Telephone => definition.telephone = Some(section.telephone()),
+                _ => (),
+            }
+        }
+        Ok(definition)
+    }
+
+    /// Load a glibc locale definition file; categories copied from another
+    /// locale are loaded from the file of that name in the same directory.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        info!("LocaleDefinition::load - loading {:?}", path.as_ref());
+        LocaleDefinition::load_with_copies(path.as_ref(), MAX_COPY_DEPTH)
+    }
+
+    /// Load the named locale definition from the system's glibc locale
+    /// sources, for example `de_DE`.
+    pub fn load_system(name: &str) -> Result<Self> {
+        LocaleDefinition::load(Path::new(SYSTEM_I18N_DIR).join("locales").join(name))
+    }
+
+    /// Return any values that are not present in their registry, or are
+    /// inconsistent with other values.
+    pub fn issues(&self) -> Vec<DefinitionIssue> {
+        let mut issues: Vec<DefinitionIssue> = Vec::new();
+        let mut issue = |category: LocaleCategory, keyword: &str, value: &str| {
+            issues.push(DefinitionIssue {
+                category,
+                keyword: keyword.to_string(),
+                value: value.to_string(),
+            })
+        };
+        if let Some(monetary) = &self.monetary {
+            if !monetary.int_curr_symbol.is_empty() && monetary.currency().is_none() {
+                issue(
+                    LocaleCategory::Monetary,
+                    "int_curr_symbol",
+                    &monetary.int_curr_symbol,
+                );
+            }
+        }
+        let country = self.address.as_ref().and_then(|address| address.country());
+        if let Some(address) = &self.address {
+            for (keyword, value) in [
+                ("country_ab2", &address.country_ab2),
+                ("country_ab3", &address.country_ab3),
+            ] {
+                if let Some(value) = value {
+                    let matched = country::lookup(value).is_some_and(|info| {
+                        country.is_some_and(|country| info.code == country.code)
+                    });
+                    if !matched {
+                        issue(LocaleCategory::Address, keyword, value);
+                    }
+                }
+            }
+            if let Some(value) = address.country_num {
+                if country.is_none_or(|country| country.country_code != value) {
+                    issue(LocaleCategory::Address, "country_num", &value.to_string());
+                }
+            }
+            let language = address.language();
+            for (keyword, value) in [
+                ("lang_ab", &address.lang_ab),
+                ("lang_term", &address.lang_term),
+            ] {
+                if let Some(value) = value {
+                    let matched = language::lookup(value).is_some_and(|info| {
+                        language.is_some_and(|language| info.code == language.code)
+                    });
+                    if !matched {
+                        issue(LocaleCategory::Address, keyword, value);
+                    }
+                }
+            }
+        }
+        if let (Some(telephone), Some(country)) = (&self.telephone, country) {
+            if let Some(calling_code) = telephone::lookup(&country.short_code) {
+                if !telephone.int_prefix.is_empty()
+                    && telephone.int_prefix != calling_code.calling_code.to_string()
+                {
+                    issue(
+                        LocaleCategory::Telephone,
+                        "int_prefix",
+                        &telephone.int_prefix,
+                    );
+                }
+            }
+        }
+        issues
+    }
+
+    fn load_with_copies(path: &Path, depth: usize) -> Result<Self> {
+        let mut definition = LocaleDefinition::parse(&fs::read_to_string(path)?)?;
+        if depth == 0 {
+            return Ok(definition);
+        }
+        let directory = path.parent().unwrap_or_else(|| Path::new("."));
+        for (category, source) in definition.copies.clone() {
+            let copied = LocaleDefinition::load_with_copies(&directory.join(source), depth - 1)?;
+            match category {
+                LocaleCategory::Monetary => definition.monetary = copied.monetary,
+                LocaleCategory::Numeric => definition.numeric = copied.numeric,
+                LocaleCategory::Time => definition.time = copied.time,
+                LocaleCategory::Address => definition.address = copied.address,
+                LocaleCategory::Telephone => definition.telephone = copied.telephone,
+                _ => (),
+            }
+        }
+        Ok(definition)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+const MAX_COPY_DEPTH: usize = 8;
+
+#[derive(Debug, Default)]
+struct Section {
+    copy: Option<String>,
+    values: HashMap<String, Vec<String>>,
+}
+
+impl Section {
+    fn string(&self, keyword: &str) -> String {
+        self.values
+            .get(keyword)
+            .and_then(|values| values.first())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn optional_string(&self, keyword: &str) -> Option<String> {
+        Some(self.string(keyword)).filter(|value| !value.is_empty())
+    }
+
+    fn strings(&self, keyword: &str) -> Vec<String> {
+        self.values.get(keyword).cloned().unwrap_or_default()
+    }
+
+    fn integers(&self, keyword: &str) -> Vec<i32> {
+        self.strings(keyword)
+            .iter()
+            .filter_map(|value| value.parse::<i32>().ok())
+            .collect()
+    }
+
+    fn integer(&self, keyword: &str) -> Option<i32> {
+        self.integers(keyword).first().cloned()
+    }
+
+    fn monetary(&self) -> MonetaryFormat {
+        MonetaryFormat {
+            int_curr_symbol: self.string("int_curr_symbol"),
+            currency_symbol: self.string("currency_symbol"),
+            mon_decimal_point: self.string("mon_decimal_point"),
+            mon_thousands_sep: self.string("mon_thousands_sep"),
+            mon_grouping: self.integers("mon_grouping"),
+            positive_sign: self.string("positive_sign"),
+            negative_sign: self.string("negative_sign"),
+            int_frac_digits: self.integer("int_frac_digits"),
+            frac_digits: self.integer("frac_digits"),
+            p_cs_precedes: self.integer("p_cs_precedes").map(|value| value == 1),
+            n_cs_precedes: self.integer("n_cs_precedes").map(|value| value == 1),
+        }
+    }
+
+    fn numeric(&self) -> NumericFormat {
+        NumericFormat {
+            decimal_point: self.string("decimal_point"),
+            thousands_sep: self.string("thousands_sep"),
+            grouping: self.integers("grouping"),
+        }
+    }
+
+    fn time(&self) -> TimeFormat {
+        TimeFormat {
+            abday: self.strings("abday"),
+            day: self.strings("day"),
+            abmon: self.strings("abmon"),
+            mon: self.strings("mon"),
+            am_pm: self.strings("am_pm"),
+            d_t_fmt: self.string("d_t_fmt"),
+            d_fmt: self.string("d_fmt"),
+            t_fmt: self.string("t_fmt"),
+            t_fmt_ampm: self.string("t_fmt_ampm"),
+            first_weekday: self.integer("first_weekday"),
+        }
+    }
+
+    fn address(&self) -> AddressFormat {
+        AddressFormat {
+            postal_fmt: self.string("postal_fmt"),
+            country_name: self.string("country_name"),
+            country_ab2: self.optional_string("country_ab2"),
+            country_ab3: self.optional_string("country_ab3"),
+            country_num: self
+                .integer("country_num")
+                .and_then(|value| u16::try_from(value).ok()),
+            lang_name: self.string("lang_name"),
+            lang_ab: self.optional_string("lang_ab"),
+            lang_term: self.optional_string("lang_term"),
+        }
+    }
+
+    fn telephone(&self) -> TelephoneFormat {
+        TelephoneFormat {
+            tel_int_fmt: self.string("tel_int_fmt"),
+            tel_dom_fmt: self.string("tel_dom_fmt"),
+            int_select: self.string("int_select"),
+            int_prefix: self.string("int_prefix"),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn invalid_data(line: &str) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("invalid glibc locale data: {}", line),
+    )
+}
+
+fn category_for_name(name: &str) -> Option<LocaleCategory> {
+    LocaleCategory::all()
+        .iter()
+        .find(|category| category.variable_name() == name)
+        .cloned()
+}
+
+fn parse_sections(content: &str) -> Result<Vec<(LocaleCategory, Section)>> {
+    let mut comment_char = '#';
+    let mut escape_char = '\\';
+    let mut sections: Vec<(LocaleCategory, Section)> = Vec::new();
+    let mut current: Option<(String, Section)> = None;
+    let mut lines = content.lines();
+    while let Some(line) = lines.next() {
+        let mut line = line.trim().to_string();
+        while line.ends_with(escape_char) && !line.ends_with(&format!("{0}{0}", escape_char)) {
+            line.pop();
+            match lines.next() {
+                Some(next) => line.push_str(next.trim()),
+                None => return Err(invalid_data(&line)),
+            }
+        }
+        if line.is_empty() || line.starts_with(comment_char) {
+            continue;
+        }
+        let (keyword, value) = match line.split_once(char::is_whitespace) {
+            Some((keyword, value)) => (keyword, value.trim()),
+            None => (line.as_str(), ""),
+        };
+        match current.as_mut() {
+            None if keyword == "comment_char" => {
+                comment_char = value.chars().next().ok_or_else(|| invalid_data(&line))?;
+            }
+            None if keyword == "escape_char" => {
+                escape_char = value.chars().next().ok_or_else(|| invalid_data(&line))?;
+            }
+            None if keyword.starts_with("LC_") => {
+                current = Some((keyword.to_string(), Section::default()));
+            }
+            None => return Err(invalid_data(&line)),
+            Some((name, section)) => {
+                let kept = category_for_name(name).is_some_and(is_kept_category);
+                if keyword == "END" {
+                    if value != name {
+                        return Err(invalid_data(&line));
+                    }
+                    let (name, section) = current.take().unwrap();
+                    if let Some(category) = category_for_name(&name) {
+                        sections.push((category, section));
+                    }
+                } else if !kept {
+                    // the content of other categories, such as LC_COLLATE,
+                    // is not in the keyword and value form.
+                    continue;
+                } else if keyword == "copy" {
+                    section.copy = parse_values(value, comment_char, escape_char)
+                        .ok_or_else(|| invalid_data(&line))?
+                        .into_iter()
+                        .next();
+                } else {
+                    let values = parse_values(value, comment_char, escape_char)
+                        .ok_or_else(|| invalid_data(&line))?;
+                    section.values.insert(keyword.to_string(), values);
+                }
+            }
+        }
+    }
+    match current {
+        Some((name, _)) => Err(invalid_data(&name)),
+        None => Ok(sections),
+    }
+}
+
+fn is_kept_category(category: LocaleCategory) -> bool {
+    matches!(
+        category,
+        LocaleCategory::Monetary
+            | LocaleCategory::Numeric
+            | LocaleCategory::Time
+            | LocaleCategory::Address
+            | LocaleCategory::Telephone
+    )
+}
+
+fn parse_values(value: &str, comment_char: char, escape_char: char) -> Option<Vec<String>> {
+    let mut values: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => loop {
+                match chars.next()? {
+                    '"' => break,
+                    c if c == escape_char => current.push(chars.next()?),
+                    '<' => current.push(parse_symbol(&mut chars)?),
+                    c => current.push(c),
+                }
+            },
+            '<' => current.push(parse_symbol(&mut chars)?),
+            ';' => values.push(std::mem::take(&mut current)),
+            c if c == comment_char => break,
+            c if c.is_whitespace() => (),
+            c => current.push(c),
+        }
+    }
+    values.push(current);
+    Some(values)
+}
+
+fn parse_symbol(chars: &mut Chars<'_>) -> Option<char> {
+    let mut symbol = String::new();
+    loop {
+        match chars.next()? {
+            '>' => break,
+            c => symbol.push(c),
+        }
+    }
+    let code_point = symbol.strip_prefix('U')?;
+    char::from_u32(u32::from_str_radix(code_point, 16).ok()?)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DE_CH: &str = r#"comment_char %
+escape_char /
+% German locale for Switzerland
+
+LC_MONETARY
+int_curr_symbol     "CHF "
+currency_symbol     "CHF"
+mon_decimal_point   "."
+mon_thousands_sep   "<U2019>"
+mon_grouping        3;3
+positive_sign       ""
+negative_sign       "-"
+int_frac_digits     2
+frac_digits         2
+p_cs_precedes       1
+n_cs_precedes       1
+END LC_MONETARY
+
+LC_NUMERIC
+copy "de_DE"
+END LC_NUMERIC
+
+LC_TIME
+abday	"So";"Mo";/
+	"Di";"Mi";"Do";"Fr";"Sa"
+d_fmt   "%d.%m.%Y"
+first_weekday 2
+END LC_TIME
+
+LC_ADDRESS
+postal_fmt    "%f%N%a%N%d%N%b%N%s %h %e %r%N%z %T%N%c%N"
+country_name  "Schweiz"
+country_ab2   "CH"
+country_ab3   "CHE"
+country_num   756
+lang_name     "Deutsch"
+lang_ab       "de"
+lang_term     "deu"
+END LC_ADDRESS
+
+LC_TELEPHONE
+tel_int_fmt    "+%c %a %l"
+int_select     "00"
+int_prefix     "41"
+END LC_TELEPHONE
+"#;
+
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn test_parse_definition() {
+        let definition = LocaleDefinition::parse(DE_CH).unwrap();
+        let monetary = definition.monetary.as_ref().unwrap();
+        assert_eq!(monetary.currency().unwrap().alphabetic_code, "CHF");
+        assert_eq!(monetary.mon_thousands_sep, "\u{2019}");
+        assert_eq!(monetary.p_cs_precedes, Some(true));
+        assert!(definition.numeric.is_none());
+        assert_eq!(
+            definition.copies.get(&LocaleCategory::Numeric),
+            Some(&"de_DE".to_string())
+        );
+        let time = definition.time.as_ref().unwrap();
+        assert_eq!(time.abday, vec!["So", "Mo", "Di", "Mi", "Do", "Fr", "Sa"]);
+        assert_eq!(time.d_fmt, "%d.%m.%Y");
+        assert_eq!(time.first_weekday, Some(2));
+        let address = definition.address.as_ref().unwrap();
+        assert_eq!(address.country().unwrap().short_code, "CH");
+        assert_eq!(address.language().unwrap().code, "deu");
+        assert_eq!(definition.telephone.as_ref().unwrap().int_prefix, "41");
+        assert!(definition.issues().is_empty());
+    }
+
+    #[test]
+    fn test_definition_issues() {
+        let content = DE_CH
+            .replace("\"CHF \"", "\"XYZ \"")
+            .replace("\"CHE\"", "\"AUT\"")
+            .replace("\"41\"", "\"43\"");
+        let issues: Vec<String> = LocaleDefinition::parse(&content)
+            .unwrap()
+            .issues()
+            .iter()
+            .map(|issue| format!("{}:{}", issue.keyword, issue.value))
+            .collect();
+        assert_eq!(
+            issues,
+            vec!["int_curr_symbol:XYZ ", "country_ab3:AUT", "int_prefix:43"]
+        );
+        assert!(LocaleDefinition::parse("LC_TIME\nd_fmt \"%d\"\n").is_err());
+        assert!(LocaleDefinition::parse("LC_TIME\nd_fmt \"%d\nEND LC_TIME\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_supported_and_aliases() {
+        let supported =
+            parse_supported("# comment\naa_ER@saaho UTF-8\nde_CH.UTF-8 UTF-8\nde_CH ISO-8859-1\n")
+                .unwrap();
+        assert_eq!(supported.len(), 3);
+        assert_eq!(
+            supported[0].locale().unwrap().modifier,
+            Some("saaho".to_string())
+        );
+        assert_eq!(supported[2].codeset().unwrap().mib_code, 4);
+        assert!(parse_supported("de_CH\n").is_err());
+        let aliases = parse_locale_aliases(
+            "# comment\n\ngerman\t\tde_DE.ISO-8859-1\nnynorsk nn_NO.ISO-8859-1\n",
+        )
+        .unwrap();
+        assert_eq!(aliases[0].alias, "german");
+        assert_eq!(aliases[1].locale().unwrap().language, "nn");
+        assert!(parse_locale_aliases("german").is_err());
+    }
+
+    #[test]
+    fn test_load_system_files() {
+        let locales = Path::new(SYSTEM_I18N_DIR).join("locales");
+        if locales.join("de_CH").exists() {
+            let definition = LocaleDefinition::load_system("de_CH").unwrap();
+            assert!(definition.numeric.is_some());
+            assert_eq!(
+                definition.address.unwrap().country().unwrap().short_code,
+                "CH"
+            );
+        }
+        if Path::new(SYSTEM_I18N_DIR).join("SUPPORTED").exists() {
+            assert!(!load_system_supported().unwrap().is_empty());
+        }
+        if Path::new(SYSTEM_LOCALE_ALIAS_FILE).exists() {
+            assert!(load_system_locale_aliases().is_ok());
+        }
+        assert!(LocaleDefinition::load("/does/not/exist/de_CH").is_err());
+    }
+}
@@ -32,13 +32,15 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::CodeStatus;
+
 // ------------------------------------------------------------------------------------------------
 // Public Types
 // ------------------------------------------------------------------------------------------------
 
 /// The ISO 639 data identifies 3 classes of languages, each language is
 /// one of these classes only.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum LanguageClass {
     /// An individual language
     Individual,
@@ -50,7 +52,7 @@ pub enum LanguageClass {
 
 /// The type of the language in this this meaning is more concerning it's
 /// current usage.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum LanguageType {
     /// Ancient, but not extinct
     Ancient,
@@ -92,6 +94,17 @@ pub struct LanguageInfo {
     pub family_members: Option<Vec<String>>,
 }
 
+/// An iterator over the registered languages, in code order, that may be
+/// narrowed using the `filter_class`, `filter_type`, and `with_short_code`
+/// methods. Created by the [`iter`](fn.iter.html) function.
+#[derive(Clone, Debug)]
+pub struct LanguageIter {
+    inner: std::slice::Iter<'static, &'static LanguageInfo>,
+    class: Option<LanguageClass>,
+    l_type: Option<LanguageType>,
+    short_code_only: bool,
+}
+
 // ------------------------------------------------------------------------------------------------
 // Public Functions
 // ------------------------------------------------------------------------------------------------
@@ -99,17 +112,14 @@ pub struct LanguageInfo {
 lazy_static! {
     static ref LANGUAGES: HashMap<String, LanguageInfo> = load_languages_from_json();
     static ref LOOKUP: HashMap<String, String> = make_language_lookup();
+    static ref ORDERED: Vec<&'static LanguageInfo> = make_ordered_languages();
 }
 
 /// Lookup a `LanguageInfo` based on it's ISO-639 2, or 3, character
 /// identifier, returning `None` if the name does not exist in the
-/// current ISO data set.
+/// current ISO data set, or is not 2, or 3, characters long.
 pub fn lookup(code: &str) -> Option<&'static LanguageInfo> {
     debug!("language::lookup {}", code);
-    assert!(
-        code.len() == 2 || code.len() == 3,
-        "language code must be either 2, or 3, characters long."
-    );
     match code.len() {
         3 => match LANGUAGES.get(code) {
             Some(v) => Some(v),
@@ -126,11 +136,99 @@ pub fn lookup(code: &str) -> Option<&'static LanguageInfo> {
     }
 }
 
+/// Classify an ISO-639 2, or 3, character identifier; the range `qaa`
+/// to `qtz` is reserved for local, private, use.
+pub fn code_status(code: &str) -> CodeStatus {
+    let bytes = code.as_bytes();
+    if !bytes.iter().all(u8::is_ascii_lowercase) {
+        CodeStatus::Unassigned
+    } else if bytes.len() == 3 && bytes[0] == b'q' && bytes[1] <= b't' {
+        CodeStatus::PrivateUse
+    } else if lookup(code).is_some() {
+        CodeStatus::Assigned
+    } else {
+        CodeStatus::Unassigned
+    }
+}
+
 /// Return all the registered ISO-639 2-character language codes.
 pub fn all_codes() -> Vec<String> {
     LANGUAGES.keys().cloned().collect()
 }
 
+/// Return an iterator over all the registered languages, ordered by their
+/// ISO 3-character identifier.
+///
+/// ```
+/// use locale_codes::language::{self, LanguageClass, LanguageType};
+///
+/// let living: Vec<&str> = language::iter()
+///     .filter_class(LanguageClass::Individual)
+///     .filter_type(LanguageType::Living)
+///     .with_short_code()
+///     .map(|l| l.code.as_str())
+///     .collect();
+/// assert!(living.contains(&"eng"));
+/// ```
+pub fn iter() -> LanguageIter {
+    LanguageIter {
+        inner: ORDERED.iter(),
+        class: None,
+        l_type: None,
+        short_code_only: false,
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl LanguageIter {
+    /// Only return languages of the given class.
+    pub fn filter_class(self, class: LanguageClass) -> Self {
+        LanguageIter {
+            class: Some(class),
+            ..self
+        }
+    }
+
+    /// Only return languages of the given type.
+    pub fn filter_type(self, l_type: LanguageType) -> Self {
+        LanguageIter {
+            l_type: Some(l_type),
+            ..self
+        }
+    }
+
+    /// Only return languages that also have an ISO 639-1 2-character
+    /// identifier.
+    pub fn with_short_code(self) -> Self {
+        LanguageIter {
+            short_code_only: true,
+            ..self
+        }
+    }
+
+    fn matches(&self, language: &LanguageInfo) -> bool {
+        (self.class.is_none() || self.class == Some(language.class))
+            && (self.l_type.is_none() || self.l_type == Some(language.l_type))
+            && (!self.short_code_only || language.short_code.is_some())
+    }
+}
+
+impl Iterator for LanguageIter {
+    type Item = &'static LanguageInfo;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(language) = self.inner.next() {
+            if self.matches(language) {
+                return Some(language);
+            }
+        }
+        None
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 // Generated Data
 // ------------------------------------------------------------------------------------------------
@@ -161,6 +259,12 @@ fn make_language_lookup() -> HashMap<String, String> {
     lookup_map
 }
 
+fn make_ordered_languages() -> Vec<&'static LanguageInfo> {
+    let mut ordered: Vec<&'static LanguageInfo> = LANGUAGES.values().collect();
+    ordered.sort_by(|lhs, rhs| lhs.code.cmp(&rhs.code));
+    ordered
+}
+
 // ------------------------------------------------------------------------------------------------
 // Unit Tests
 // ------------------------------------------------------------------------------------------------
@@ -179,4 +283,45 @@ mod tests {
             Some(l) => println!("test_language_loading {:#?}", to_string_pretty(l)),
         }
     }
+
+    #[test]
+    fn test_bad_language_code() {
+        assert!(lookup("xx").is_none());
+        assert!(lookup("").is_none());
+        assert!(lookup("english").is_none());
+    }
+
+    #[test]
+    fn test_language_code_status() {
+        assert_eq!(code_status("fra"), CodeStatus::Assigned);
+        assert_eq!(code_status("fr"), CodeStatus::Assigned);
+        assert_eq!(code_status("qab"), CodeStatus::PrivateUse);
+        assert_eq!(code_status("qtz"), CodeStatus::PrivateUse);
+        assert_eq!(code_status("qua"), CodeStatus::Assigned);
+        assert_eq!(code_status("zz"), CodeStatus::Unassigned);
+        assert_eq!(code_status("Fra"), CodeStatus::Unassigned);
+        assert_eq!(code_status("english"), CodeStatus::Unassigned);
+    }
+
+    #[test]
+    fn test_language_iter_ordered() {
+        let codes: Vec<&str> = iter().map(|l| l.code.as_str()).collect();
+        assert_eq!(codes.len(), all_codes().len());
+        assert!(codes.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_language_iter_filters() {
+        let languages: Vec<&LanguageInfo> = iter()
+            .filter_class(LanguageClass::Individual)
+            .filter_type(LanguageType::Living)
+            .with_short_code()
+            .collect();
+        assert!(!languages.is_empty());
+        assert!(languages.iter().any(|l| l.code == "fra"));
+        // Arabic is a macrolanguage, Latin is ancient.
+        assert!(!languages.iter().any(|l| l.code == "ara"));
+        assert!(!languages.iter().any(|l| l.code == "lat"));
+        assert!(languages.iter().all(|l| l.short_code.is_some()));
+    }
 }
@@ -19,6 +19,9 @@ are definitely some patterns all of the current implementations follow.
 1. Most will also include a function `all_codes()` to retrieve a vector of all
    the known identifiers,
 1. or, `all_alpha_codes()` and `all_numeric_codes()` as appropriate.
+1. Modules for standards that set aside ranges of codes also implement a
+   `code_status()` function that returns a [`CodeStatus`](enum.CodeStatus.html),
+   so that private-use codes can be distinguished from simple typos.
 
 Some standards, specifically language and country, support 2-character and
 3-character alphabetic identifiers, a single `lookup()` function is used to
@@ -66,11 +69,23 @@ Currently data is generated for the following standards:
 * ISO 639 _Codes for the representation of names of languages_; Parts 1-4,
   2-character and 3-character codes supported.
 * ISO 3166 _Codes for the representation of names of countries and their
-  subdivisions_; Part 1, 2-character codes, only.
+  subdivisions_; Part 1, 2- and 3-character codes, and Part 3 formerly
+  used codes.
 * ISO 4217 _Codes for the representation of currencies_; alphabetic and
   numeric codes supported.
 * ISO 15924 _Codes for the representation of names of scripts_; alphabetic
   and numeric codes supported.
+* ITU-T E.164 _The international public telecommunication numbering plan_;
+  country calling codes and dialing prefixes.
+* IANA Time Zone Database; the timezones used in each country.
+* Postal code and postal address formats for each country.
+* Unicode CLDR; the languages spoken in each country, the likely script
+  and country for each language, the parent locales used for fallback, the
+  replacements for deprecated codes, the Unicode locale extension keys
+  and types, and the localized names of languages, scripts, territories and
+  currencies.
+* Microsoft MS-LCID; the Windows locale identifiers for each language and
+  country.
 
 Each folder under `src-data` represents a single standard, which may
 generate one or more data sets. Each directory will contain a Python
@@ -91,18 +106,71 @@ extern crate lazy_static;
 #[macro_use]
 extern crate log;
 
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// The status of a code with respect to the standard that defines it. Most
+/// standards set aside some ranges of codes for private use, and reserve
+/// others so that they will not be assigned in the future.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CodeStatus {
+    /// The code is assigned, and may be used in a `lookup()` function.
+    Assigned,
+    /// The code falls within a range set aside for private, or user-assigned,
+    /// use; it will never be assigned by the standard itself.
+    PrivateUse,
+    /// The code is reserved by the standard, but is not currently assigned.
+    Reserved,
+    /// The code was formerly assigned, but has since been withdrawn and is
+    /// no longer reserved.
+    Withdrawn,
+    /// The code is not assigned, or is not well-formed.
+    Unassigned,
+}
+
 // ------------------------------------------------------------------------------------------------
 // Public Modules
 // ------------------------------------------------------------------------------------------------
 
+pub mod address;
+
+pub mod canonical;
+
 pub mod codeset;
 
 pub mod country;
 
+pub mod crossref;
+
 pub mod currency;
 
+pub mod dialect;
+
+pub mod display_name;
+
+pub mod fallback;
+
+pub mod glibc;
+
 pub mod language;
 
+pub mod likely_subtags;
+
+pub mod negotiation;
+
+pub mod posix;
+
 pub mod region;
 
 pub mod script;
+
+pub mod search;
+
+pub mod telephone;
+
+pub mod territory;
+
+pub mod timezone;
+
+pub mod unicode_extension;
@@ -0,0 +1,384 @@
+/*!
+Likely subtags, inferring the most likely script and country for a language.
+
+A locale is often identified by a partial set of subtags, `zh` for Chinese,
+`und-TW` for an unknown language in Taiwan. The Unicode CLDR _likely subtags_
+data records the most likely complete identifier for these; `zh` is most
+likely written in the simplified Han script in China, `zh-Hans-CN`, while
+`und-TW` is most likely Chinese written in the traditional Han script,
+`zh-Hant-TW`.
+
+The [`maximize`](fn.maximize.html) function adds likely subtags, the
+[`minimize`](fn.minimize.html) function removes any that `maximize` would add
+back. Both follow the algorithms described in
+[UTS #35](https://www.unicode.org/reports/tr35/#Likely_Subtags), and each
+subtag is validated against the [`language`](../language/index.html),
+[`script`](../script/index.html) and [`country`](../country/index.html)
+registries. A UN M.49 numeric region from the [`region`](../region/index.html)
+registry, such as `419` for Latin America, may be used in place of a country;
+`und-419` is most likely Spanish, `es-Latn-419`. The language `und` is used
+where the language is unknown.
+
+```
+use locale_codes::likely_subtags;
+
+let chinese = likely_subtags::maximize("zh", None, None).unwrap();
+assert_eq!(chinese.to_string(), "zh-Hans-CN");
+
+let serbian = likely_subtags::minimize("sr", Some("Cyrl"), Some("RS")).unwrap();
+assert_eq!(serbian.to_string(), "sr");
+
+let latin = likely_subtags::minimize("sr", Some("Latn"), Some("RS")).unwrap();
+assert_eq!(latin.to_string(), "sr-Latn");
+```
+
+## Source - CLDR
+
+The data used here is taken from the `likelySubtags` data in the Unicode
+[CLDR](http://cldr.unicode.org/) supplemental data.
+*/
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{country, language, region, script};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// The language, script and country, or region, subtags of a locale
+/// identifier.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LocaleSubtags {
+    /// The ISO-639 identifier of the language, the 2-character identifier
+    /// where one exists, or `und` if the language is unknown.
+    pub language: String,
+    /// The ISO-15924 4-character identifier of the script.
+    pub script: Option<String>,
+    /// The ISO-3166 2-character identifier of the country.
+    pub country: Option<String>,
+    /// The UN M.49 3-digit identifier of a region that is not a country,
+    /// such as `419`; present only where `country` is not.
+    #[serde(default)]
+    pub region: Option<String>,
+}
+
+/// The language code used where the language is undetermined.
+pub const UNDETERMINED: &str = "und";
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+lazy_static! {
+    static ref LIKELY_SUBTAGS: HashMap<String, LocaleSubtags> = load_likely_subtags_from_json();
+}
+
+/// Add the most likely script and country to the given subtags; any subtag
+/// provided is retained. Returns `None` if any of the subtags are not
+/// present in their registry.
+pub fn maximize(
+    language: &str,
+    script: Option<&str>,
+    country: Option<&str>,
+) -> Option<LocaleSubtags> {
+    debug!(
+        "likely_subtags::maximize: {} {:?} {:?}",
+        language, script, country
+    );
+    let subtags = LocaleSubtags::new(language, script, country)?;
+    Some(subtags.maximize())
+}
+
+/// Remove any script and country subtags that would be added back by
+/// `maximize`, preferring to retain the country over the script where
+/// either would do. Returns `None` if any of the subtags are not present in
+/// their registry.
+pub fn minimize(
+    language: &str,
+    script: Option<&str>,
+    country: Option<&str>,
+) -> Option<LocaleSubtags> {
+    debug!(
+        "likely_subtags::minimize: {} {:?} {:?}",
+        language, script, country
+    );
+    let subtags = LocaleSubtags::new(language, script, country)?;
+    Some(subtags.minimize())
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for LocaleSubtags {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.language)?;
+        if let Some(script) = &self.script {
+            write!(f, "-{}", script)?;
+        }
+        if let Some(country) = self.country.as_ref().or(self.region.as_ref()) {
+            write!(f, "-{}", country)?;
+        }
+        Ok(())
+    }
+}
+
+impl LocaleSubtags {
+    /// Construct a new set of subtags, normalizing the case of each and
+    /// using the 2-character language and country identifiers where they
+    /// exist. The country may also be a UN M.49 numeric code; the code of a
+    /// country is replaced by its 2-character identifier, any other is kept
+    /// as the region. Returns `None` if any of the subtags are not present in
+    /// their registry.
+    pub fn new(language: &str, script: Option<&str>, country: Option<&str>) -> Option<Self> {
+        let (country, region) = match country {
+            Some(code) if code.len() == 3 && code.chars().all(|c| c.is_ascii_digit()) => {
+                normalize_numeric_region(code)?
+            }
+            Some(code) => (Some(normalize_country(code)?), None),
+            None => (None, None),
+        };
+        Some(LocaleSubtags {
+            language: normalize_language(language)?,
+            script: match script {
+                Some(script) => Some(normalize_script(script)?),
+                None => None,
+            },
+            country,
+            region,
+        })
+    }
+
+    /// Parse a locale identifier of the form `language[-script][-country]`,
+    /// such as `zh-Hant-TW` or `es-419`; either `-` or `_` may separate the
+    /// subtags.
+    /// Returns `None` if the identifier has any other subtags, or if any of
+    /// the subtags are not present in their registry.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let mut subtags = tag.split(['-', '_']);
+        let language = subtags.next()?;
+        let mut script = None;
+        let mut country = None;
+        for subtag in subtags {
+            if subtag.len() == 4 && script.is_none() && country.is_none() {
+                script = Some(subtag);
+            } else if country.is_none() {
+                country = Some(subtag);
+            } else {
+                return None;
+            }
+        }
+        LocaleSubtags::new(language, script, country)
+    }
+
+    /// Returns `true` if the language is `und`, undetermined.
+    pub fn is_undetermined(&self) -> bool {
+        self.language == UNDETERMINED
+    }
+
+    /// Returns `true` if the language, script and country, or region, are
+    /// all present.
+    pub fn is_complete(&self) -> bool {
+        !self.is_undetermined()
+            && self.script.is_some()
+            && (self.country.is_some() || self.region.is_some())
+    }
+
+    /// Add the most likely script and country to these subtags, see the
+    /// module function `maximize`.
+    pub fn maximize(&self) -> Self {
+        let language = self.language.as_str();
+        let script = self.script.as_deref();
+        let country = self.country.as_deref().or(self.region.as_deref());
+        let candidates = [
+            (language, script, country),
+            (language, None, country),
+            (language, script, None),
+            (language, None, None),
+            (UNDETERMINED, script, None),
+            (UNDETERMINED, None, None),
+        ];
+        let likely = candidates
+            .iter()
+            .find_map(|(language, script, country)| {
+                LIKELY_SUBTAGS.get(&make_key(language, *script, *country))
+            })
+            .unwrap();
+        LocaleSubtags {
+            language: if self.is_undetermined() {
+                likely.language.clone()
+            } else {
+                self.language.clone()
+            },
+            script: self.script.clone().or_else(|| likely.script.clone()),
+            country: if country.is_some() {
+                self.country.clone()
+            } else {
+                likely.country.clone()
+            },
+            region: if country.is_some() {
+                self.region.clone()
+            } else {
+                likely.region.clone()
+            },
+        }
+    }
+
+    /// Remove any script and country subtags that would be added back by
+    /// `maximize`, see the module function `minimize`.
+    pub fn minimize(&self) -> Self {
+        let maximized = self.maximize();
+        let trials = [
+            (None, None, None),
+            (None, maximized.country.clone(), maximized.region.clone()),
+            (maximized.script.clone(), None, None),
+        ];
+        for (script, country, region) in trials.iter() {
+            let trial = LocaleSubtags {
+                language: maximized.language.clone(),
+                script: script.clone(),
+                country: country.clone(),
+                region: region.clone(),
+            };
+            if trial.maximize() == maximized {
+                return trial;
+            }
+        }
+        maximized
+    }
+}
+
+/// Returns `true` if the likely subtags data has an entry for the language
+/// on its own.
+pub(crate) fn has_language(language: &str) -> bool {
+    LIKELY_SUBTAGS.contains_key(language)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+pub(crate) fn normalize_language(language: &str) -> Option<String> {
+    let language = language.to_ascii_lowercase();
+    if language == UNDETERMINED {
+        Some(language)
+    } else {
+        let info = language::lookup(&language)?;
+        Some(info.short_code.as_ref().unwrap_or(&info.code).to_string())
+    }
+}
+
+pub(crate) fn normalize_script(script: &str) -> Option<String> {
+    if script.len() != 4 || !script.is_ascii() {
+        return None;
+    }
+    let script = format!(
+        "{}{}",
+        script[..1].to_ascii_uppercase(),
+        script[1..].to_ascii_lowercase()
+    );
+    script::lookup_by_alpha(&script).map(|info| info.alphabetic_code.to_string())
+}
+
+fn normalize_numeric_region(code: &str) -> Option<(Option<String>, Option<String>)> {
+    let code = code.parse::<u16>().ok()?;
+    match country::lookup_by_numeric(code) {
+        Some(country) => Some((Some(country.short_code.to_string()), None)),
+        None => {
+            region::lookup(code)?;
+            Some((None, Some(format!("{:03}", code))))
+        }
+    }
+}
+
+pub(crate) fn normalize_country(country: &str) -> Option<String> {
+    country::lookup(&country.to_ascii_uppercase()).map(|info| info.short_code.to_string())
+}
+
+fn make_key(language: &str, script: Option<&str>, country: Option<&str>) -> String {
+    let mut key = language.to_string();
+    for subtag in [script, country].iter().flatten() {
+        key.push('_');
+        key.push_str(subtag);
+    }
+    key
+}
+
+// ------------------------------------------------------------------------------------------------
+// Generated Data
+// ------------------------------------------------------------------------------------------------
+
+fn load_likely_subtags_from_json() -> HashMap<String, LocaleSubtags> {
+    info!("load_likely_subtags_from_json - loading JSON");
+    let raw_data = include_bytes!("data/likely_subtags.json");
+    let likely_map: HashMap<String, LocaleSubtags> = serde_json::from_slice(raw_data).unwrap();
+    info!(
+        "load_likely_subtags_from_json - loaded {} entries",
+        likely_map.len()
+    );
+    likely_map
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn test_maximize() {
+        let max = |language: &str, script: Option<&str>, country: Option<&str>| -> String {
+            maximize(language, script, country).unwrap().to_string()
+        };
+        assert_eq!(max("zh", None, None), "zh-Hans-CN");
+        assert_eq!(max("zho", None, None), "zh-Hans-CN");
+        assert_eq!(max("zh", None, Some("TW")), "zh-Hant-TW");
+        assert_eq!(max("zh", Some("Hant"), None), "zh-Hant-TW");
+        assert_eq!(max("und", None, Some("TW")), "zh-Hant-TW");
+        assert_eq!(max("und", Some("Cyrl"), None), "ru-Cyrl-RU");
+        assert_eq!(max("sr", Some("latn"), None), "sr-Latn-RS");
+        assert_eq!(max("fr", Some("Hant"), None), "fr-Hant-FR");
+        assert_eq!(max("und", None, None), "en-Latn-US");
+        assert_eq!(max("EN", None, Some("gbr")), "en-Latn-GB");
+        assert!(maximize("qaa", None, None).is_none());
+        assert!(maximize("en", Some("Xxxx"), None).is_none());
+        assert_eq!(max("und", None, Some("419")), "es-Latn-419");
+        assert_eq!(max("en", None, Some("419")), "en-Latn-419");
+        assert_eq!(max("eo", None, None), "eo-Latn-001");
+        assert_eq!(max("de", None, Some("276")), "de-Latn-DE");
+        assert!(maximize("en", None, Some("999")).is_none());
+    }
+
+    #[test]
+    fn test_minimize() {
+        let min = |language: &str, script: Option<&str>, country: Option<&str>| -> String {
+            minimize(language, script, country).unwrap().to_string()
+        };
+        assert_eq!(min("zh", Some("Hans"), Some("CN")), "zh");
+        assert_eq!(min("zh", Some("Hant"), Some("TW")), "zh-TW");
+        assert_eq!(min("zh", Some("Hant"), None), "zh-TW");
+        assert_eq!(min("sr", Some("Cyrl"), Some("RS")), "sr");
+        assert_eq!(min("sr", Some("Latn"), None), "sr-Latn");
+        assert_eq!(min("en", None, Some("GB")), "en-GB");
+        assert_eq!(min("und", None, Some("DE")), "de");
+        let subtags = LocaleSubtags::new("und", None, Some("JP")).unwrap();
+        assert!(!subtags.is_complete());
+        assert!(subtags.maximize().is_complete());
+        let parsed = LocaleSubtags::parse("zh_hant-tw").unwrap();
+        assert_eq!(parsed.to_string(), "zh-Hant-TW");
+        assert_eq!(LocaleSubtags::parse("en-GB").unwrap().to_string(), "en-GB");
+        assert!(LocaleSubtags::parse("en-GB-oxendict").is_none());
+        let parsed = LocaleSubtags::parse("es-419").unwrap();
+        assert!(parsed.country.is_none());
+        assert_eq!(parsed.region, Some("419".to_string()));
+        assert_eq!(min("es", Some("Latn"), Some("419")), "es-419");
+        assert!(LocaleSubtags::parse("").is_none());
+    }
+}
@@ -0,0 +1,528 @@
+/*!
+Locale negotiation, choosing a supported locale from a user's preferences.
+
+A client, such as a web browser, sends a list of language ranges in order of
+preference, commonly in the HTTP `Accept-Language` header:
+
+```text
+Accept-Language: fr-CH, fr;q=0.9, en;q=0.8, *;q=0.5
+```
+
+The [`parse_accept_language`](fn.parse_accept_language.html) function parses
+such a list into `LanguageRange` values, ordered by their quality weight; each
+range is validated against the [`language`](../language/index.html),
+[`script`](../script/index.html) and [`country`](../country/index.html)
+registries and any range that is not is dropped. The region may also be one
+of the UN M.49 numeric codes in the [`region`](../region/index.html)
+registry, such as `419` in `es-419`, which is held in the `region` of the
+range's subtags rather than as a country; any variants or extensions are
+truncated, so that `en-GB-oxendict` is matched as `en-GB`. The ranges can
+then be matched against the locales an application supports in one of three
+modes.
+
+* `MatchMode::Filter`, RFC 4647 _basic filtering_, returns every supported
+  locale that matches a range, where a range matches a locale if it is equal
+  to it, or to a prefix of it; `fr` matches `fr-CH`, but `fr-CH` does not
+  match `fr`.
+* `MatchMode::Lookup`, RFC 4647 _lookup_, returns the first supported locale
+  equal to a range; where there is none each range is progressively
+  truncated, `fr-CH` matches `fr`, but `fr` does not match `fr-CH`.
+* `MatchMode::BestMatch` scores every pair of range and supported locale
+  and returns the highest scoring locale. The subtags of each are first
+  maximized using [`likely_subtags`](../likely_subtags/index.html), so that
+  `zh-TW` matches `zh-Hant`, and languages are related using the
+  macrolanguage `family_members` in `LanguageInfo`, so that `nb` matches `no`.
+
+In all modes the chosen locale has a score between `0.0` and `1.0`, the
+product of the range's quality weight and the quality of the match.
+
+```
+use locale_codes::negotiation::{self, MatchMode};
+
+let header = "fr-CH, fr;q=0.9, en;q=0.8, *;q=0.5";
+let supported = ["en", "fr-FR", "de"];
+
+let chosen = negotiation::negotiate(header, &supported, MatchMode::Lookup).unwrap();
+assert_eq!(chosen.locale, "en");
+
+let chosen = negotiation::negotiate(header, &supported, MatchMode::BestMatch).unwrap();
+assert_eq!(chosen.locale, "fr-FR");
+assert_eq!(chosen.range, "fr-CH");
+```
+
+## Source - RFC 4647
+
+The matching schemes are described in
+[RFC 4647](https://tools.ietf.org/html/rfc4647), _Matching of Language Tags_,
+and the `Accept-Language` header in
+[RFC 7231](https://tools.ietf.org/html/rfc7231#section-5.3.5).
+*/
+
+use std::collections::HashMap;
+
+use crate::language::{self, LanguageClass};
+use crate::likely_subtags::{self, LocaleSubtags, UNDETERMINED};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// A language range from a list of preferences, such as the HTTP
+/// `Accept-Language` header.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LanguageRange {
+    /// The range, as written.
+    pub range: String,
+    /// The validated language, script and country subtags of the range,
+    /// `None` for the wildcard range `*` which matches any locale.
+    pub subtags: Option<LocaleSubtags>,
+    /// The quality weight of the range, between `0.0` and `1.0`.
+    pub quality: f32,
+}
+
+/// The scheme used to match language ranges against supported locales.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchMode {
+    /// RFC 4647 basic filtering.
+    Filter,
+    /// RFC 4647 lookup.
+    Lookup,
+    /// Score all ranges against all locales, using likely subtags and
+    /// macrolanguages, and pick the best.
+    BestMatch,
+}
+
+/// A supported locale chosen by negotiation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NegotiatedLocale {
+    /// The supported locale, as provided.
+    pub locale: String,
+    /// The language range it was matched by, as written.
+    pub range: String,
+    /// The quality of the match, between `0.0` and `1.0`.
+    pub score: f32,
+}
+
+/// The match quality where a range is a macrolanguage of a locale's
+/// language, or the reverse; for example `no` and `nb`.
+pub const MACROLANGUAGE_MATCH: f32 = 0.9;
+
+/// The match quality where a range and a locale's language are both members
+/// of the same macrolanguage; for example `nb` and `nn`.
+pub const SIBLING_MATCH: f32 = 0.5;
+
+/// The factor applied to the match quality where the scripts differ.
+pub const SCRIPT_MISMATCH: f32 = 0.3;
+
+/// The factor applied to the match quality where the countries differ.
+pub const COUNTRY_MISMATCH: f32 = 0.9;
+
+/// The match quality of the wildcard range `*`.
+pub const WILDCARD_MATCH: f32 = 0.5;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+lazy_static! {
+    static ref MACROLANGUAGES: HashMap<String, String> = make_macrolanguage_lookup();
+}
+
+/// Parse a list of language ranges, in the form of the HTTP `Accept-Language`
+/// header, into `LanguageRange` values ordered by descending quality weight.
+/// Ranges of equal weight retain their order; malformed ranges, ranges that
+/// are not in the registries, and ranges with a weight of `0` are dropped.
+pub fn parse_accept_language(header: &str) -> Vec<LanguageRange> {
+    debug!("negotiation::parse_accept_language: {}", header);
+    let mut ranges: Vec<LanguageRange> = header
+        .split(',')
+        .filter_map(parse_range)
+        .filter(|range| range.quality > 0.0)
+        .collect();
+    ranges.sort_by(|lhs, rhs| rhs.quality.partial_cmp(&lhs.quality).unwrap());
+    ranges
+}
+
+/// Return all the supported locales matched by the ranges, using RFC 4647
+/// basic filtering, ordered by the range they were first matched by.
+/// Supported locales that are not in the registries are ignored.
+pub fn filter_locales(ranges: &[LanguageRange], supported: &[&str]) -> Vec<NegotiatedLocale> {
+    let supported = parse_supported(supported);
+    let mut matched: Vec<NegotiatedLocale> = Vec::new();
+    for range in ranges {
+        let prefix = range.subtags.as_ref().map(|subtags| subtags.to_string());
+        for (locale, subtags) in &supported {
+            let tag = subtags.to_string();
+            let is_match = match &prefix {
+                Some(prefix) => tag == *prefix || tag.starts_with(&format!("{}-", prefix)),
+                None => true,
+            };
+            if is_match && !matched.iter().any(|chosen| chosen.locale == *locale) {
+                matched.push(NegotiatedLocale::new(range, locale, subtags));
+            }
+        }
+    }
+    matched
+}
+
+/// Return the first supported locale matched by the ranges, using RFC 4647
+/// lookup; the wildcard range is ignored. Supported locales that are not in
+/// the registries are ignored.
+pub fn lookup_locale(ranges: &[LanguageRange], supported: &[&str]) -> Option<NegotiatedLocale> {
+    let supported = parse_supported(supported);
+    for range in ranges {
+        if let Some(subtags) = &range.subtags {
+            let mut candidates = vec![subtags.clone()];
+            if subtags.country.is_some() || subtags.region.is_some() {
+                candidates.push(LocaleSubtags {
+                    country: None,
+                    region: None,
+                    ..subtags.clone()
+                });
+            }
+            if subtags.script.is_some() {
+                candidates.push(LocaleSubtags {
+                    language: subtags.language.clone(),
+                    script: None,
+                    country: None,
+                    region: None,
+                });
+            }
+            for candidate in candidates {
+                if let Some((locale, subtags)) =
+                    supported.iter().find(|(_, subtags)| *subtags == candidate)
+                {
+                    return Some(NegotiatedLocale::new(range, locale, subtags));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Return the supported locale with the highest score against any of the
+/// ranges, where scores are equal the earlier range, and then the earlier
+/// locale, is chosen. Supported locales that are not in the registries are
+/// ignored.
+pub fn best_match(ranges: &[LanguageRange], supported: &[&str]) -> Option<NegotiatedLocale> {
+    let supported = parse_supported(supported);
+    let mut best: Option<NegotiatedLocale> = None;
+    for range in ranges {
+        for (locale, subtags) in &supported {
+            let candidate = NegotiatedLocale::new(range, locale, subtags);
+            if candidate.score > 0.0
+                && best
+                    .as_ref()
+                    .is_none_or(|chosen| candidate.score > chosen.score)
+            {
+                best = Some(candidate);
+            }
+        }
+    }
+    best
+}
+
+/// Parse the HTTP `Accept-Language` header and choose one of the supported
+/// locales using the given match mode; for `MatchMode::Filter` this is the
+/// first locale matched.
+pub fn negotiate(header: &str, supported: &[&str], mode: MatchMode) -> Option<NegotiatedLocale> {
+    let ranges = parse_accept_language(header);
+    match mode {
+        MatchMode::Filter => filter_locales(&ranges, supported).into_iter().next(),
+        MatchMode::Lookup => lookup_locale(&ranges, supported),
+        MatchMode::BestMatch => best_match(&ranges, supported),
+    }
+}
+
+/// Return the quality of the match between the desired subtags and those of
+/// a supported locale, between `0.0` for no match and `1.0` for an exact
+/// match once both are maximized. A UN M.49 region takes the place of the
+/// likely country of either side.
+pub fn match_quality(desired: &LocaleSubtags, supported: &LocaleSubtags) -> f32 {
+    let desired = maximize_for_match(desired);
+    let supported = maximize_for_match(supported);
+    let mut quality = language_match(&desired.language, &supported.language);
+    if desired.script != supported.script {
+        quality *= SCRIPT_MISMATCH;
+    }
+    if desired.country.as_ref().or(desired.region.as_ref())
+        != supported.country.as_ref().or(supported.region.as_ref())
+    {
+        quality *= COUNTRY_MISMATCH;
+    }
+    quality
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl LanguageRange {
+    /// Returns `true` if this is the wildcard range `*`.
+    pub fn is_wildcard(&self) -> bool {
+        self.subtags.is_none()
+    }
+}
+
+impl NegotiatedLocale {
+    fn new(range: &LanguageRange, locale: &str, subtags: &LocaleSubtags) -> Self {
+        let quality = match &range.subtags {
+            Some(desired) => match_quality(desired, subtags),
+            None => WILDCARD_MATCH,
+        };
+        NegotiatedLocale {
+            locale: locale.to_string(),
+            range: range.range.clone(),
+            score: range.quality * quality,
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn parse_range(range: &str) -> Option<LanguageRange> {
+    let mut parts = range.split(';').map(str::trim);
+    let tag = parts.next()?;
+    let mut quality = 1.0;
+    for parameter in parts {
+        let value = parameter
+            .strip_prefix("q=")
+            .or_else(|| parameter.strip_prefix("Q="))?;
+        quality = value.parse::<f32>().ok()?;
+        if !(0.0..=1.0).contains(&quality) {
+            return None;
+        }
+    }
+    let subtags = if tag == "*" {
+        None
+    } else {
+        Some(parse_tag(tag)?)
+    };
+    Some(LanguageRange {
+        range: tag.to_string(),
+        subtags,
+        quality,
+    })
+}
+
+fn parse_supported<'a>(supported: &[&'a str]) -> Vec<(&'a str, LocaleSubtags)> {
+    supported
+        .iter()
+        .filter_map(|locale| parse_tag(locale).map(|subtags| (*locale, subtags)))
+        .collect()
+}
+
+// a tag is reduced to its language, script and region subtags; variants and
+// extensions, such as `oxendict` in `en-GB-oxendict`, are truncated as in
+// RFC 4647 lookup.
+fn parse_tag(tag: &str) -> Option<LocaleSubtags> {
+    let mut subtags = tag.split(['-', '_']);
+    let language = subtags.next()?;
+    let mut script = None;
+    let mut region = None;
+    let mut truncated = false;
+    for subtag in subtags {
+        if subtag.is_empty()
+            || subtag.len() > 8
+            || !subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            return None;
+        }
+        if truncated {
+            continue;
+        }
+        if subtag.len() == 4
+            && subtag.chars().all(|c| c.is_ascii_alphabetic())
+            && script.is_none()
+            && region.is_none()
+        {
+            script = Some(subtag);
+        } else if (subtag.len() == 2 || subtag.len() == 3) && region.is_none() {
+            region = Some(subtag);
+        } else if is_variant_or_singleton(subtag) {
+            truncated = true;
+        } else {
+            return None;
+        }
+    }
+    LocaleSubtags::new(language, script, region)
+}
+
+fn is_variant_or_singleton(subtag: &str) -> bool {
+    subtag.len() == 1
+        || subtag.len() >= 5
+        || (subtag.len() == 4 && subtag.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+fn maximize_for_match(subtags: &LocaleSubtags) -> LocaleSubtags {
+    // an individual language without likely subtags of its own, such as
+    // `cmn`, is likely to share those of its macrolanguage, `zh`.
+    if !subtags.is_undetermined() && !likely_subtags::has_language(&subtags.language) {
+        if let Some(macrolanguage) = macrolanguage_of(&subtags.language) {
+            let maximized = LocaleSubtags {
+                language: macrolanguage,
+                ..subtags.clone()
+            }
+            .maximize();
+            return LocaleSubtags {
+                language: subtags.language.clone(),
+                ..maximized
+            };
+        }
+    }
+    subtags.maximize()
+}
+
+fn language_match(desired: &str, supported: &str) -> f32 {
+    if desired == supported {
+        return 1.0;
+    }
+    let desired_macro = macrolanguage_of(desired);
+    let supported_macro = macrolanguage_of(supported);
+    if desired_macro.as_deref() == Some(supported) || supported_macro.as_deref() == Some(desired) {
+        MACROLANGUAGE_MATCH
+    } else if desired_macro.is_some() && desired_macro == supported_macro {
+        SIBLING_MATCH
+    } else {
+        0.0
+    }
+}
+
+fn macrolanguage_of(language: &str) -> Option<String> {
+    if language == UNDETERMINED {
+        return None;
+    }
+    let macrolanguage = language::lookup(MACROLANGUAGES.get(&language::lookup(language)?.code)?)?;
+    Some(
+        macrolanguage
+            .short_code
+            .as_ref()
+            .unwrap_or(&macrolanguage.code)
+            .to_string(),
+    )
+}
+
+fn make_macrolanguage_lookup() -> HashMap<String, String> {
+    info!("make_macrolanguage_lookup - create from language::iter");
+    let mut lookup_map: HashMap<String, String> = HashMap::new();
+    for macrolanguage in language::iter().filter_class(LanguageClass::MacroLanguage) {
+        if let Some(members) = &macrolanguage.family_members {
+            for member in members {
+                lookup_map.insert(member.to_string(), macrolanguage.code.to_string());
+            }
+        }
+    }
+    info!(
+        "make_macrolanguage_lookup - mapped {} languages",
+        lookup_map.len()
+    );
+    lookup_map
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn test_parse_accept_language() {
+        let ranges = parse_accept_language("fr-CH, fr;q=0.9, en;q=0.8, de;q=0.9, *;q=0.5");
+        let names: Vec<&str> = ranges.iter().map(|range| range.range.as_str()).collect();
+        assert_eq!(names, vec!["fr-CH", "fr", "de", "en", "*"]);
+        assert_eq!(ranges[1].quality, 0.9);
+        assert!(ranges[4].is_wildcard());
+        let ranges = parse_accept_language("en;q=0, xx-YY, en-ZZ, de;q=2, fr;level=1, it");
+        let names: Vec<&str> = ranges.iter().map(|range| range.range.as_str()).collect();
+        assert_eq!(names, vec!["it"]);
+        assert!(parse_accept_language("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_regions_and_variants() {
+        let ranges = parse_accept_language("es-419,en;q=0.5");
+        let names: Vec<&str> = ranges.iter().map(|range| range.range.as_str()).collect();
+        assert_eq!(names, vec!["es-419", "en"]);
+        let spanish = ranges[0].subtags.as_ref().unwrap();
+        assert_eq!(spanish.to_string(), "es-419");
+        assert!(spanish.country.is_none());
+        assert_eq!(spanish.region, Some("419".to_string()));
+        assert!(ranges[1].subtags.as_ref().unwrap().region.is_none());
+        let ranges = parse_accept_language("en-GB-oxendict, de-CH-1901, sl-rozaj-biske");
+        let subtags: Vec<String> = ranges
+            .iter()
+            .map(|range| range.subtags.as_ref().unwrap().to_string())
+            .collect();
+        assert_eq!(subtags, vec!["en-GB", "de-CH", "sl"]);
+        assert_eq!(ranges[0].range, "en-GB-oxendict");
+        assert!(parse_accept_language("es-999, en-GB-419, en-GB-x-").is_empty());
+
+        let supported = ["es-ES", "es-419", "en"];
+        let filtered: Vec<String> = filter_locales(&parse_accept_language("es"), &supported)
+            .into_iter()
+            .map(|chosen| chosen.locale)
+            .collect();
+        assert_eq!(filtered, vec!["es-ES", "es-419"]);
+        let chosen = negotiate("es-419", &supported, MatchMode::Lookup).unwrap();
+        assert_eq!(chosen.locale, "es-419");
+        let chosen = negotiate("es-419", &["es-ES", "es-419"], MatchMode::BestMatch).unwrap();
+        assert_eq!(chosen.locale, "es-419");
+        let chosen = negotiate("es-419", &["en", "es"], MatchMode::Lookup).unwrap();
+        assert_eq!(chosen.locale, "es");
+        let chosen = negotiate("en-GB-oxendict", &["en-US", "en"], MatchMode::Lookup).unwrap();
+        assert_eq!(chosen.locale, "en");
+        assert_eq!(chosen.range, "en-GB-oxendict");
+    }
+
+    #[test]
+    fn test_filter_and_lookup() {
+        let supported = ["en-US", "en-GB", "fr", "fr-CA", "zz-ZZ"];
+        let locales = |mode: MatchMode, header: &str| -> Vec<String> {
+            let ranges = parse_accept_language(header);
+            match mode {
+                MatchMode::Filter => filter_locales(&ranges, &supported)
+                    .into_iter()
+                    .map(|chosen| chosen.locale)
+                    .collect(),
+                _ => lookup_locale(&ranges, &supported)
+                    .into_iter()
+                    .map(|chosen| chosen.locale)
+                    .collect(),
+            }
+        };
+        assert_eq!(locales(MatchMode::Filter, "en"), vec!["en-US", "en-GB"]);
+        assert_eq!(
+            locales(MatchMode::Filter, "fr-CA, en-GB"),
+            vec!["fr-CA", "en-GB"]
+        );
+        assert!(locales(MatchMode::Filter, "fr-CH").is_empty());
+        assert_eq!(locales(MatchMode::Filter, "de, *").len(), 4);
+        assert_eq!(locales(MatchMode::Lookup, "fr-CH, en"), vec!["fr"]);
+        assert_eq!(locales(MatchMode::Lookup, "en, fr"), vec!["fr"]);
+        assert!(locales(MatchMode::Lookup, "de, *").is_empty());
+    }
+
+    #[test]
+    fn test_best_match() {
+        let best = |header: &str, supported: &[&str]| -> Option<String> {
+            negotiate(header, supported, MatchMode::BestMatch).map(|chosen| chosen.locale)
+        };
+        assert_eq!(best("en-AU", &["fr", "en-GB"]), Some("en-GB".to_string()));
+        assert_eq!(
+            best("zh-TW", &["zh-Hans", "zh-Hant"]),
+            Some("zh-Hant".to_string())
+        );
+        assert_eq!(best("nb", &["da", "no"]), Some("no".to_string()));
+        assert_eq!(best("cmn", &["en", "zh-CN"]), Some("zh-CN".to_string()));
+        assert_eq!(best("de, *;q=0.1", &["en", "fr"]), Some("en".to_string()));
+        assert_eq!(best("de", &["en", "fr"]), None);
+        let chosen = negotiate("fr-CH, en;q=0.8", &["en", "fr"], MatchMode::BestMatch).unwrap();
+        assert_eq!(chosen.range, "fr-CH");
+        assert!((chosen.score - COUNTRY_MISMATCH).abs() < f32::EPSILON);
+    }
+}
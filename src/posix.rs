@@ -0,0 +1,483 @@
+/*!
+The locale of the current process, as set in POSIX environment variables.
+
+On POSIX systems the locale is set for a number of categories, such as
+`LC_TIME` for date and time formatting or `LC_MESSAGES` for translated
+messages, using environment variables. For each category the locale is
+taken from the first of the following variables that is set and not empty.
+
+1. `LC_ALL`, which overrides all other settings,
+1. the variable named for the category, such as `LC_TIME`,
+1. `LANG`, the default for all categories,
+1. otherwise, the `C` locale is used.
+
+A locale name has the form `language[_territory][.codeset][@modifier]`, for
+example `de_CH.UTF-8` or `sr_RS@latin`; the names `C` and `POSIX`, and the
+codeset variants such as `C.UTF-8`, identify the minimal POSIX locale. The
+language, territory and codeset in a name are validated against the
+[`language`](../language/index.html), [`country`](../country/index.html)
+and [`codeset`](../codeset/index.html) registries.
+
+The GNU `LANGUAGE` variable holds a list of locale names, separated by `:`,
+to use for translated messages in order of priority; it is ignored where the
+locale for `LC_MESSAGES` is the `C` locale.
+
+```
+use std::collections::HashMap;
+use locale_codes::posix::{self, LocaleCategory};
+
+let mut environment: HashMap<String, String> = HashMap::new();
+environment.insert("LANG".to_string(), "en_GB.UTF-8".to_string());
+environment.insert("LC_TIME".to_string(), "de_CH.utf8".to_string());
+environment.insert("LANGUAGE".to_string(), "fr_CA:fr:en".to_string());
+
+let locale = posix::from_environment(&environment);
+let time = locale.category(LocaleCategory::Time);
+assert_eq!(time.variable, Some("LC_TIME".to_string()));
+assert_eq!(time.locale.as_ref().unwrap().territory, Some("CH".to_string()));
+assert_eq!(locale.category(LocaleCategory::Numeric).value, "en_GB.UTF-8");
+assert_eq!(locale.languages.len(), 3);
+```
+*/
+
+use std::collections::HashMap;
+use std::env;
+use std::ffi::OsString;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::codeset::{self, CodesetInfo};
+use crate::country::{self, CountryInfo};
+use crate::language::{self, LanguageInfo};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// The categories of locale-dependent behavior, each set by the environment
+/// variable of the same name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LocaleCategory {
+    /// `LC_CTYPE`, character classification and case conversion.
+    CType,
+    /// `LC_NUMERIC`, non-monetary numeric formats.
+    Numeric,
+    /// `LC_TIME`, date and time formats.
+    Time,
+    /// `LC_COLLATE`, collation order.
+    Collate,
+    /// `LC_MONETARY`, monetary formats.
+    Monetary,
+    /// `LC_MESSAGES`, the language of messages.
+    Messages,
+    /// `LC_PAPER`, paper size; a GNU extension.
+    Paper,
+    /// `LC_NAME`, personal name formats; a GNU extension.
+    Name,
+    /// `LC_ADDRESS`, postal address formats; a GNU extension.
+    Address,
+    /// `LC_TELEPHONE`, telephone number formats; a GNU extension.
+    Telephone,
+    /// `LC_MEASUREMENT`, the measurement system; a GNU extension.
+    Measurement,
+    /// `LC_IDENTIFICATION`, metadata about the locale; a GNU extension.
+    Identification,
+}
+
+/// A parsed, and validated, POSIX locale name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PosixLocale {
+    /// The ISO-639 2, or 3, character language identifier.
+    pub language: String,
+    /// The ISO-3166 2-character identifier of the territory.
+    pub territory: Option<String>,
+    /// The name of the codeset, as written.
+    pub codeset: Option<String>,
+    /// The modifier, such as `euro` or `latin`.
+    pub modifier: Option<String>,
+}
+
+/// The locale for a single category, and where it was set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CategoryLocale {
+    /// The category.
+    pub category: LocaleCategory,
+    /// The environment variable the locale was taken from, `None` if no
+    /// variable was set and the `C` locale is used.
+    pub variable: Option<String>,
+    /// The locale name, as set.
+    pub value: String,
+    /// The parsed locale, `None` for the `C` locale or if the name is not
+    /// valid.
+    pub locale: Option<PosixLocale>,
+}
+
+/// The locale of a process, for each category.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnvironmentLocale {
+    /// The locale for each category, in the order of `LocaleCategory::all`.
+    pub categories: Vec<CategoryLocale>,
+    /// The valid locales in the GNU `LANGUAGE` priority list.
+    pub languages: Vec<PosixLocale>,
+}
+
+/// The name of the minimal POSIX locale.
+pub const C_LOCALE: &str = "C";
+
+/// An alternative name for the minimal POSIX locale.
+pub const POSIX_LOCALE: &str = "POSIX";
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+/// Determine the locale for each category from the given environment
+/// variables.
+pub fn from_environment(environment: &HashMap<String, String>) -> EnvironmentLocale {
+    let variable = |name: &str| -> Option<(String, String)> {
+        environment
+            .get(name)
+            .filter(|value| !value.is_empty())
+            .map(|value| (name.to_string(), value.to_string()))
+    };
+    let categories: Vec<CategoryLocale> = LocaleCategory::all()
+        .iter()
+        .map(|category| {
+            match variable("LC_ALL")
+                .or_else(|| variable(category.variable_name()))
+                .or_else(|| variable("LANG"))
+            {
+                Some((name, value)) => CategoryLocale {
+                    category: *category,
+                    variable: Some(name),
+                    locale: PosixLocale::parse(&value),
+                    value,
+                },
+                None => CategoryLocale {
+                    category: *category,
+                    variable: None,
+                    value: C_LOCALE.to_string(),
+                    locale: None,
+                },
+            }
+        })
+        .collect();
+    let messages = &categories[LocaleCategory::Messages as usize];
+    let languages = match variable("LANGUAGE") {
+        Some((_, value)) if !messages.is_posix() => {
+            value.split(':').filter_map(PosixLocale::parse).collect()
+        }
+        _ => Vec::new(),
+    };
+    EnvironmentLocale {
+        categories,
+        languages,
+    }
+}
+
+/// Determine the locale for each category from the environment of the
+/// current process. Variables whose name or value is not valid Unicode are
+/// converted lossily, so that such a value is reported as set but invalid.
+pub fn current() -> EnvironmentLocale {
+    from_environment(&environment_from_os(env::vars_os()))
+}
+
+/// Returns `true` if the locale name identifies the minimal POSIX locale,
+/// `C` or `POSIX`, with or without a codeset.
+pub fn is_posix_name(name: &str) -> bool {
+    let name = name.split(['.', '@']).next().unwrap_or_default();
+    name == C_LOCALE || name == POSIX_LOCALE
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for LocaleCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.variable_name())
+    }
+}
+
+impl LocaleCategory {
+    /// Return all the categories.
+    pub fn all() -> [LocaleCategory; 12] {
+        [
+            LocaleCategory::CType,
+            LocaleCategory::Numeric,
+            LocaleCategory::Time,
+            LocaleCategory::Collate,
+            LocaleCategory::Monetary,
+            LocaleCategory::Messages,
+            LocaleCategory::Paper,
+            LocaleCategory::Name,
+            LocaleCategory::Address,
+            LocaleCategory::Telephone,
+            LocaleCategory::Measurement,
+            LocaleCategory::Identification,
+        ]
+    }
+
+    /// Return the name of the environment variable for this category.
+    pub fn variable_name(&self) -> &'static str {
+        match self {
+            LocaleCategory::CType => "LC_CTYPE",
+            LocaleCategory::Numeric => "LC_NUMERIC",
+            LocaleCategory::Time => "LC_TIME",
+            LocaleCategory::Collate => "LC_COLLATE",
+            LocaleCategory::Monetary => "LC_MONETARY",
+            LocaleCategory::Messages => "LC_MESSAGES",
+            LocaleCategory::Paper => "LC_PAPER",
+            LocaleCategory::Name => "LC_NAME",
+            LocaleCategory::Address => "LC_ADDRESS",
+            LocaleCategory::Telephone => "LC_TELEPHONE",
+            LocaleCategory::Measurement => "LC_MEASUREMENT",
+            LocaleCategory::Identification => "LC_IDENTIFICATION",
+        }
+    }
+}
+
+impl Display for PosixLocale {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.language)?;
+        if let Some(territory) = &self.territory {
+            write!(f, "_{}", territory)?;
+        }
+        if let Some(codeset) = &self.codeset {
+            write!(f, ".{}", codeset)?;
+        }
+        if let Some(modifier) = &self.modifier {
+            write!(f, "@{}", modifier)?;
+        }
+        Ok(())
+    }
+}
+
+impl PosixLocale {
+    /// Parse a locale name of the form
+    /// `language[_territory][.codeset][@modifier]`, returning `None` if the
+    /// name is malformed, if any part is not present in its registry, or if
+    /// it is the `C` locale.
+    pub fn parse(name: &str) -> Option<Self> {
+        if is_posix_name(name) {
+            return None;
+        }
+        let (name, modifier) = match name.split_once('@') {
+            Some((name, modifier)) if !modifier.is_empty() => (name, Some(modifier.to_string())),
+            Some(_) => return None,
+            None => (name, None),
+        };
+        let (name, codeset) = match name.split_once('.') {
+            Some((name, codeset)) if !codeset.is_empty() => (name, Some(codeset.to_string())),
+            Some(_) => return None,
+            None => (name, None),
+        };
+        let (language, territory) = match name.split_once('_') {
+            Some((language, territory)) => (language, Some(territory.to_string())),
+            None => (name, None),
+        };
+        let locale = PosixLocale {
+            language: language.to_string(),
+            territory,
+            codeset,
+            modifier,
+        };
+        if locale.language().is_some()
+            && (locale.territory.is_none() || locale.country().is_some())
+            && (locale.codeset.is_none() || locale.codeset_info().is_some())
+        {
+            Some(locale)
+        } else {
+            None
+        }
+    }
+
+    /// Return the `LanguageInfo` for this locale's language.
+    pub fn language(&self) -> Option<&'static LanguageInfo> {
+        match self.language.len() {
+            2 | 3 => language::lookup(&self.language),
+            _ => None,
+        }
+    }
+
+    /// Return the `CountryInfo` for this locale's territory, if any.
+    pub fn country(&self) -> Option<&'static CountryInfo> {
+        match self.territory.as_ref() {
+            Some(territory) if territory.len() == 2 => country::lookup(territory),
+            _ => None,
+        }
+    }
+
+    /// Return the `CodesetInfo` for this locale's codeset, if any.
+    pub fn codeset_info(&self) -> Option<&'static CodesetInfo> {
+        match self.codeset.as_ref() {
+            Some(codeset) => codeset::lookup_by_alias(codeset),
+            None => None,
+        }
+    }
+}
+
+impl CategoryLocale {
+    /// Returns `true` if this category uses the `C` locale.
+    pub fn is_posix(&self) -> bool {
+        is_posix_name(&self.value)
+    }
+
+    /// Returns `true` if this category uses the `C` locale, or a valid
+    /// named locale.
+    pub fn is_valid(&self) -> bool {
+        self.is_posix() || self.locale.is_some()
+    }
+}
+
+impl EnvironmentLocale {
+    /// Return the locale for the given category.
+    pub fn category(&self, category: LocaleCategory) -> &CategoryLocale {
+        &self.categories[category as usize]
+    }
+
+    /// Return the locales to use for translated messages, in order of
+    /// priority; the `LANGUAGE` list if set, otherwise the `LC_MESSAGES`
+    /// locale, if valid.
+    pub fn message_locales(&self) -> Vec<&PosixLocale> {
+        if self.languages.is_empty() {
+            self.category(LocaleCategory::Messages)
+                .locale
+                .iter()
+                .collect()
+        } else {
+            self.languages.iter().collect()
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn environment_from_os<I>(variables: I) -> HashMap<String, String>
+where
+    I: IntoIterator<Item = (OsString, OsString)>,
+{
+    variables
+        .into_iter()
+        .map(|(name, value)| {
+            (
+                name.to_string_lossy().into_owned(),
+                value.to_string_lossy().into_owned(),
+            )
+        })
+        .collect()
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn environment(variables: &[(&str, &str)]) -> HashMap<String, String> {
+        variables
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn test_parse_locale_name() {
+        let locale = PosixLocale::parse("sr_RS.UTF-8@latin").unwrap();
+        assert_eq!(locale.language, "sr");
+        assert_eq!(locale.territory, Some("RS".to_string()));
+        assert_eq!(locale.codeset_info().unwrap().name, "UTF-8");
+        assert_eq!(locale.modifier, Some("latin".to_string()));
+        assert_eq!(locale.to_string(), "sr_RS.UTF-8@latin");
+        assert_eq!(
+            PosixLocale::parse("ast_ES")
+                .unwrap()
+                .language()
+                .unwrap()
+                .code,
+            "ast"
+        );
+        assert!(PosixLocale::parse("de").unwrap().territory.is_none());
+        assert!(PosixLocale::parse("C.UTF-8").is_none());
+        assert!(PosixLocale::parse("xx_YY").is_none());
+        assert!(PosixLocale::parse("en_ZZ").is_none());
+        assert!(PosixLocale::parse("en_US.utf99").is_none());
+        assert!(PosixLocale::parse("en_US.").is_none());
+        assert!(PosixLocale::parse("").is_none());
+    }
+
+    #[test]
+    fn test_precedence() {
+        let locale = from_environment(&environment(&[
+            ("LANG", "en_US.UTF-8"),
+            ("LC_TIME", "en_GB.UTF-8"),
+            ("LC_MONETARY", ""),
+        ]));
+        assert_eq!(locale.category(LocaleCategory::Time).value, "en_GB.UTF-8");
+        assert_eq!(
+            locale.category(LocaleCategory::Monetary).variable,
+            Some("LANG".to_string())
+        );
+        let locale = from_environment(&environment(&[
+            ("LANG", "en_US.UTF-8"),
+            ("LC_TIME", "en_GB.UTF-8"),
+            ("LC_ALL", "fr_FR"),
+        ]));
+        for category in &locale.categories {
+            assert_eq!(category.variable, Some("LC_ALL".to_string()));
+            assert_eq!(category.locale.as_ref().unwrap().language, "fr");
+        }
+        let locale = from_environment(&HashMap::new());
+        assert!(locale.categories.iter().all(|category| category.is_posix()));
+        assert!(locale.category(LocaleCategory::CType).variable.is_none());
+    }
+
+    #[test]
+    fn test_posix_and_language_list() {
+        let locale = from_environment(&environment(&[("LANG", "POSIX"), ("LANGUAGE", "fr:en")]));
+        assert!(locale.category(LocaleCategory::Messages).is_posix());
+        assert!(locale.languages.is_empty());
+        assert!(locale.message_locales().is_empty());
+        let locale = from_environment(&environment(&[
+            ("LANG", "de_DE.UTF-8"),
+            ("LC_CTYPE", "xx_YY"),
+            ("LANGUAGE", "pt_BR:xx:pt"),
+        ]));
+        assert!(!locale.category(LocaleCategory::CType).is_valid());
+        let languages: Vec<String> = locale
+            .message_locales()
+            .iter()
+            .map(|locale| locale.to_string())
+            .collect();
+        assert_eq!(languages, vec!["pt_BR", "pt"]);
+        let locale = from_environment(&environment(&[("LC_MESSAGES", "es_MX")]));
+        assert_eq!(locale.message_locales()[0].language, "es");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_non_unicode_environment() {
+        use std::os::unix::ffi::OsStringExt;
+
+        let locale = from_environment(&environment_from_os(vec![
+            (
+                OsString::from("LANG"),
+                OsString::from_vec(b"de_DE\xff".to_vec()),
+            ),
+            (
+                OsString::from_vec(b"LC_\xff".to_vec()),
+                OsString::from("fr_FR"),
+            ),
+            (OsString::from("LC_TIME"), OsString::from("en_GB.UTF-8")),
+        ]));
+        let ctype = locale.category(LocaleCategory::CType);
+        assert_eq!(ctype.variable, Some("LANG".to_string()));
+        assert_eq!(ctype.value, "de_DE\u{fffd}");
+        assert!(!ctype.is_valid());
+        assert!(locale.category(LocaleCategory::Time).is_valid());
+    }
+}
@@ -8,6 +8,26 @@ and subdivisions. However, it does not define the names of countries
 Bulletin Country Names and the Country and Region Codes for Statistical
 Use maintained by the United Nations Statistics Divisions).
 
+## Hierarchy - UN M.49
+
+The regions are arranged into a hierarchy by the UN _Standard country or area
+codes for statistical use_ (known as M.49); the world (`001`) contains the
+continental regions, which contain sub-regions, some of which are further
+divided into intermediate regions, and all of which eventually contain
+countries. The functions [`parent`](fn.parent.html),
+[`children`](fn.children.html), [`ancestors`](fn.ancestors.html), and
+[`countries_in`](fn.countries_in.html) navigate this hierarchy.
+
+```
+use locale_codes::region;
+
+let names: Vec<&str> = region::ancestors(276)
+    .iter()
+    .map(|r| r.name.as_str())
+    .collect();
+assert_eq!(names, vec!["Western Europe", "Europe", "World"]);
+```
+
 ## Source - ISO 3166
 
 The data used here is taken from the page
@@ -18,10 +38,27 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::country::{self, CountryInfo};
+
 // ------------------------------------------------------------------------------------------------
 // Public Types
 // ------------------------------------------------------------------------------------------------
 
+/// The level of a region within the UN M.49 hierarchy.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegionKind {
+    /// The root of the hierarchy, `001`.
+    World,
+    /// A continental region, such as `150` Europe.
+    Continent,
+    /// A sub-region, such as `155` Western Europe.
+    SubRegion,
+    /// An intermediate region, such as `029` Caribbean.
+    IntermediateRegion,
+    /// An individual country, or area.
+    Country,
+}
+
 /// A representation of registered region data maintained by ISO.
 #[derive(Deserialize, Serialize, Debug)]
 pub struct RegionInfo {
@@ -29,6 +66,11 @@ pub struct RegionInfo {
     pub code: u16,
     /// The name of this region.
     pub name: String,
+    /// The level of this region in the UN M.49 hierarchy.
+    pub kind: RegionKind,
+    /// The numeric identifier of the region that contains this one, `None`
+    /// only for the world.
+    pub parent_code: Option<u16>,
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -37,8 +79,13 @@ pub struct RegionInfo {
 
 lazy_static! {
     static ref REGIONS: HashMap<u16, RegionInfo> = load_regions_from_json();
+    static ref CHILDREN: HashMap<u16, Vec<u16>> = make_region_children();
+    static ref NAME_LOOKUP: HashMap<String, u16> = make_region_name_lookup();
 }
 
+/// The numeric identifier of the root of the region hierarchy.
+pub const WORLD: u16 = 1;
+
 /// Lookup a `RegionInfo` based on it's ISO-3166 numeric identifier, returning
 /// `None` if the name does not exist in the current ISO data set.
 pub fn lookup(code: u16) -> Option<&'static RegionInfo> {
@@ -49,11 +96,108 @@ pub fn lookup(code: u16) -> Option<&'static RegionInfo> {
     }
 }
 
+/// Lookup a `RegionInfo` based on it's name, ignoring case, returning `None`
+/// if the name does not exist in the current data set.
+///
+/// ```
+/// use locale_codes::region;
+///
+/// assert_eq!(region::lookup_by_name("western europe").unwrap().code, 155);
+/// ```
+pub fn lookup_by_name(name: &str) -> Option<&'static RegionInfo> {
+    debug!("lookup_by_name: {}", name);
+    match NAME_LOOKUP.get(&name.to_lowercase()) {
+        Some(code) => lookup(*code),
+        None => None,
+    }
+}
+
+/// Return all the aggregate regions, that is all regions excluding the
+/// individual countries, in code order.
+pub fn aggregate_regions() -> Vec<&'static RegionInfo> {
+    let mut regions: Vec<&'static RegionInfo> = REGIONS
+        .values()
+        .filter(|region| region.is_aggregate())
+        .collect();
+    regions.sort_by_key(|region| region.code);
+    regions
+}
+
+/// Return the region that directly contains the identified region, `None`
+/// if the region is unknown, or is the world.
+pub fn parent(code: u16) -> Option<&'static RegionInfo> {
+    match lookup(code) {
+        Some(region) => match region.parent_code {
+            Some(parent_code) => lookup(parent_code),
+            None => None,
+        },
+        None => None,
+    }
+}
+
+/// Return the regions directly contained in the identified region, in
+/// code order.
+pub fn children(code: u16) -> Vec<&'static RegionInfo> {
+    match CHILDREN.get(&code) {
+        Some(codes) => codes.iter().filter_map(|code| lookup(*code)).collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Return all the regions that contain the identified region, starting with
+/// it's parent and ending with the world.
+pub fn ancestors(code: u16) -> Vec<&'static RegionInfo> {
+    let mut ancestors: Vec<&'static RegionInfo> = Vec::new();
+    let mut current = parent(code);
+    while let Some(region) = current {
+        ancestors.push(region);
+        current = parent(region.code);
+    }
+    ancestors
+}
+
+/// Return all the countries contained, directly or indirectly, within the
+/// identified region, in code order. For a region of kind
+/// `RegionKind::Country` this is the country itself.
+pub fn countries_in(code: u16) -> Vec<&'static CountryInfo> {
+    let mut countries: Vec<&'static CountryInfo> = country::all_countries()
+        .filter(|country| {
+            country.country_code == code
+                || ancestors(country.country_code)
+                    .iter()
+                    .any(|region| region.code == code)
+        })
+        .collect();
+    countries.sort_by_key(|country| country.country_code);
+    countries
+}
+
 /// Return all the registered ISO-3166 numeric region codes.
 pub fn all_codes() -> Vec<u16> {
     REGIONS.keys().cloned().collect()
 }
 
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl RegionInfo {
+    /// Returns `true` if this region is an aggregate of other regions, and
+    /// `false` if it represents a single country.
+    pub fn is_aggregate(&self) -> bool {
+        self.kind != RegionKind::Country
+    }
+
+    /// Return the `CountryInfo` for a region of kind `RegionKind::Country`,
+    /// or `None` for an aggregate region.
+    pub fn country(&self) -> Option<&'static CountryInfo> {
+        match self.kind {
+            RegionKind::Country => country::lookup_by_numeric(self.code),
+            _ => None,
+        }
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 // Generated Data
 // ------------------------------------------------------------------------------------------------
@@ -62,20 +206,79 @@ fn load_regions_from_json() -> HashMap<u16, RegionInfo> {
     info!("load_regions_from_json - loading JSON");
     let raw_data = include_bytes!("data/regions.json");
     let raw_map: HashMap<String, String> = serde_json::from_slice(raw_data).unwrap();
+    let hierarchy = make_region_hierarchy();
     raw_map
         .iter()
         .map(|(code, name)| {
+            let code = code.parse::<u16>().unwrap();
+            let (kind, parent_code) = match hierarchy.get(&code) {
+                Some((kind, parent_code)) => (*kind, *parent_code),
+                None if code == WORLD => (RegionKind::World, None),
+                None => panic!("region {:03} is not placed in the M.49 hierarchy", code),
+            };
             (
-                code.parse::<u16>().unwrap(),
+                code,
                 RegionInfo {
-                    code: code.parse::<u16>().unwrap(),
+                    code,
                     name: name.to_string(),
+                    kind,
+                    parent_code,
                 },
             )
         })
         .collect()
 }
 
+fn make_region_hierarchy() -> HashMap<u16, (RegionKind, Option<u16>)> {
+    info!("make_region_hierarchy - create from countries");
+    let mut hierarchy: HashMap<u16, (RegionKind, Option<u16>)> = HashMap::new();
+    for country in country::all_countries() {
+        let mut parent_code = WORLD;
+        for (code, kind) in &[
+            (country.region_code, RegionKind::Continent),
+            (country.sub_region_code, RegionKind::SubRegion),
+            (
+                country.intermediate_region_code,
+                RegionKind::IntermediateRegion,
+            ),
+        ] {
+            if let Some(code) = code {
+                hierarchy.insert(*code, (*kind, Some(parent_code)));
+                parent_code = *code;
+            }
+        }
+        hierarchy.insert(
+            country.country_code,
+            (RegionKind::Country, Some(parent_code)),
+        );
+    }
+    info!("make_region_hierarchy - mapped {} regions", hierarchy.len());
+    hierarchy
+}
+
+fn make_region_name_lookup() -> HashMap<String, u16> {
+    REGIONS
+        .values()
+        .map(|region| (region.name.to_lowercase(), region.code))
+        .collect()
+}
+
+fn make_region_children() -> HashMap<u16, Vec<u16>> {
+    let mut children_map: HashMap<u16, Vec<u16>> = HashMap::new();
+    for region in REGIONS.values() {
+        if let Some(parent_code) = region.parent_code {
+            children_map
+                .entry(parent_code)
+                .or_default()
+                .push(region.code);
+        }
+    }
+    for children in children_map.values_mut() {
+        children.sort_unstable();
+    }
+    children_map
+}
+
 // ------------------------------------------------------------------------------------------------
 // Unit Tests
 // ------------------------------------------------------------------------------------------------
@@ -88,7 +291,7 @@ mod tests {
     #[test]
     fn test_region_codes() {
         let codes = all_codes();
-        assert!(codes.len() > 0);
+        assert!(!codes.is_empty());
     }
 
     #[test]
@@ -99,6 +302,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_region_kinds() {
+        assert_eq!(lookup(WORLD).unwrap().kind, RegionKind::World);
+        assert_eq!(lookup(150).unwrap().kind, RegionKind::Continent);
+        assert_eq!(lookup(419).unwrap().kind, RegionKind::SubRegion);
+        assert_eq!(lookup(29).unwrap().kind, RegionKind::IntermediateRegion);
+        assert_eq!(lookup(484).unwrap().kind, RegionKind::Country);
+        for region in REGIONS.values() {
+            if region.code != WORLD {
+                assert_ne!(region.kind, RegionKind::World);
+                assert!(region.parent_code.is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn test_region_navigation() {
+        let continents: Vec<u16> = children(WORLD).iter().map(|r| r.code).collect();
+        assert_eq!(continents, vec![2, 9, 10, 19, 142, 150]);
+        assert_eq!(parent(21).unwrap().code, 19);
+        assert!(parent(WORLD).is_none());
+        let codes: Vec<u16> = ancestors(388).iter().map(|r| r.code).collect();
+        assert_eq!(codes, vec![29, 419, 19, WORLD]);
+        assert!(ancestors(WORLD).is_empty());
+    }
+
+    #[test]
+    fn test_countries_in_region() {
+        let europe = countries_in(150);
+        assert!(europe.iter().any(|c| c.code == "DEU"));
+        assert!(!europe.iter().any(|c| c.code == "MEX"));
+        let latin_america = countries_in(419);
+        assert!(latin_america.iter().any(|c| c.code == "MEX"));
+        assert!(latin_america.iter().any(|c| c.code == "BRA"));
+        assert_eq!(countries_in(WORLD).len(), country::all_codes().len());
+        assert_eq!(countries_in(276).len(), 1);
+    }
+
+    #[test]
+    fn test_region_by_name() {
+        assert_eq!(lookup_by_name("Western Europe").unwrap().code, 155);
+        assert_eq!(lookup_by_name("WESTERN EUROPE").unwrap().code, 155);
+        assert_eq!(lookup_by_name("mexico").unwrap().code, 484);
+        assert!(lookup_by_name("Middle Earth").is_none());
+    }
+
+    #[test]
+    fn test_aggregate_regions() {
+        let aggregates = aggregate_regions();
+        assert!(aggregates.iter().all(|region| region.is_aggregate()));
+        assert!(aggregates.iter().any(|region| region.code == 419));
+        assert!(!aggregates.iter().any(|region| region.code == 484));
+        assert_eq!(aggregates[0].code, WORLD);
+    }
+
+    #[test]
+    fn test_region_country() {
+        assert_eq!(lookup(484).unwrap().country().unwrap().code, "MEX");
+        assert!(lookup(150).unwrap().country().is_none());
+    }
+
     #[test]
     fn test_bad_region_code() {
         match lookup(0) {
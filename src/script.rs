@@ -31,6 +31,8 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::CodeStatus;
+
 // ------------------------------------------------------------------------------------------------
 // Public Types
 // ------------------------------------------------------------------------------------------------
@@ -71,12 +73,42 @@ pub fn lookup_by_alpha(alphabetic_code: &str) -> Option<&'static ScriptInfo> {
 /// Lookup a `ScriptInfo` based on it's ISO-15924 numeric identifier, returning
 /// `None` if the name does not exist in the current ISO data set.
 pub fn lookup_by_numeric(numeric_code: &u16) -> Option<&'static ScriptInfo> {
-    match NUMERIC_LOOKUP.get(&numeric_code) {
+    match NUMERIC_LOOKUP.get(numeric_code) {
         Some(v) => lookup_by_alpha(v),
         None => None,
     }
 }
 
+/// Classify an ISO-15924 4-character identifier; the range `Qaaa` to `Qabx`
+/// is reserved for private use.
+pub fn code_status(alphabetic_code: &str) -> CodeStatus {
+    let bytes = alphabetic_code.as_bytes();
+    if bytes.len() != 4
+        || !bytes[0].is_ascii_uppercase()
+        || !bytes[1..].iter().all(u8::is_ascii_lowercase)
+    {
+        CodeStatus::Unassigned
+    } else if &bytes[..2] == b"Qa" && (bytes[2] == b'a' || (bytes[2] == b'b' && bytes[3] <= b'x')) {
+        CodeStatus::PrivateUse
+    } else if SCRIPTS.contains_key(alphabetic_code) {
+        CodeStatus::Assigned
+    } else {
+        CodeStatus::Unassigned
+    }
+}
+
+/// Classify an ISO-15924 numeric identifier; the range 900 to 949 is reserved
+/// for private use.
+pub fn numeric_code_status(numeric_code: u16) -> CodeStatus {
+    if (900..=949).contains(&numeric_code) {
+        CodeStatus::PrivateUse
+    } else if NUMERIC_LOOKUP.contains_key(&numeric_code) {
+        CodeStatus::Assigned
+    } else {
+        CodeStatus::Unassigned
+    }
+}
+
 /// Return all the registered ISO-15924 4-character country codes.
 pub fn all_alpha_codes() -> Vec<String> {
     SCRIPTS.keys().cloned().collect()
@@ -133,7 +165,7 @@ mod tests {
 
     #[test]
     fn test_bad_script_alpha_code() {
-        match lookup_by_alpha(&"UTF8") {
+        match lookup_by_alpha("UTF8") {
             None => (),
             Some(_) => panic!("was expecting a None in response"),
         }
@@ -159,11 +191,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_script_code_status() {
+        assert_eq!(code_status("Latn"), CodeStatus::Assigned);
+        assert_eq!(code_status("Qaaa"), CodeStatus::PrivateUse);
+        assert_eq!(code_status("Qaab"), CodeStatus::PrivateUse);
+        assert_eq!(code_status("Qabx"), CodeStatus::PrivateUse);
+        assert_eq!(code_status("Qaby"), CodeStatus::Unassigned);
+        assert_eq!(code_status("LATN"), CodeStatus::Unassigned);
+        assert_eq!(numeric_code_status(925), CodeStatus::PrivateUse);
+        assert_eq!(numeric_code_status(215), CodeStatus::Assigned);
+        assert_eq!(numeric_code_status(1), CodeStatus::Unassigned);
+    }
+
     #[test]
     fn test_script_codes() {
         let codes = all_alpha_codes();
-        assert!(codes.len() > 0);
+        assert!(!codes.is_empty());
         let numerics = all_numeric_codes();
-        assert!(numerics.len() > 0);
+        assert!(!numerics.is_empty());
     }
 }
@@ -0,0 +1,619 @@
+/*!
+Fuzzy search across the country, language, currency, script, region and
+codeset registries.
+
+The lookup functions in each registry require an exact code, or an exact
+name; this module finds entries from the kind of text a person would type.
+Each entry is indexed by its codes, its registry name, any aliases (such as
+`LanguageInfo::other_names`, `ScriptInfo::alias` or
+`CodesetInfo::also_known_as`), and its localized names from the
+[`display_name`](../display_name/index.html) module. Both the query and the
+indexed text are [folded](fn.fold.html) so that case, diacritics and
+punctuation are ignored, words may appear in any order, a word may be
+abbreviated to its prefix, and small typing errors are tolerated.
+
+Hits are ranked by their score, from `1.0` for an exact match of a code or
+name down towards `0.0`, and only the best hit for each entry is returned.
+
+```
+use locale_codes::search::{self, EntryKind};
+
+let hits = search::search("cote divoire", 3);
+assert_eq!(hits[0].kind, EntryKind::Country);
+assert_eq!(hits[0].code, "CI");
+
+let hits = search::search("korea south", 3);
+assert_eq!(hits[0].code, "KR");
+
+let hits = search::search_kind("swiss frank", EntryKind::Currency, 3);
+assert_eq!(hits[0].code, "CHF");
+
+let hits = search::search("cyrillic", 1);
+assert_eq!(hits[0].kind, EntryKind::Script);
+assert_eq!(hits[0].code, "Cyrl");
+```
+
+The module-level functions use an index of the names in every locale for
+which there are display names, an index over a chosen set of locales can be
+created with [`SearchIndex::new`](struct.SearchIndex.html#method.new).
+*/
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::display_name::{self, DisplayName, NameKind};
+use crate::{codeset, country, currency, language, region, script};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// The registry an entry was found in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum EntryKind {
+    /// A country, identified by its ISO-3166 2-character code.
+    Country,
+    /// An aggregate region, identified by its 3-digit UN M.49 code.
+    Region,
+    /// A language, identified by its ISO-639 3-character code.
+    Language,
+    /// A script, identified by its ISO-15924 4-character code.
+    Script,
+    /// A currency, identified by its ISO-4217 alphabetic code.
+    Currency,
+    /// A codeset, identified by its IANA name.
+    Codeset,
+}
+
+/// The part of an entry that matched the query.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MatchField {
+    /// One of the codes of the entry; codes only match exactly.
+    Code,
+    /// The name of the entry in the registry.
+    Name,
+    /// An alternative name for the entry in the registry.
+    Alias,
+    /// The name of the entry in one of the indexed locales.
+    LocalizedName,
+}
+
+/// A single search result.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchHit {
+    /// The registry the entry was found in.
+    pub kind: EntryKind,
+    /// The identifying code of the entry, as described for `EntryKind`.
+    pub code: String,
+    /// The English name of the entry.
+    pub name: String,
+    /// The text, as indexed, that matched the query.
+    pub matched: String,
+    /// The part of the entry the matched text came from.
+    pub field: MatchField,
+    /// The score of this hit, `1.0` for an exact match.
+    pub score: f64,
+}
+
+/// An index over the registries that may be searched many times.
+#[derive(Debug, Default)]
+pub struct SearchIndex {
+    entries: Vec<Entry>,
+    terms: Vec<Term>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+lazy_static! {
+    static ref INDEX: SearchIndex = make_default_index();
+}
+
+/// Return, at most, `limit` hits for the query across all registries, best
+/// first.
+pub fn search(query: &str, limit: usize) -> Vec<SearchHit> {
+    INDEX.search(query, limit)
+}
+
+/// Return, at most, `limit` hits for the query in a single registry, best
+/// first.
+pub fn search_kind(query: &str, kind: EntryKind, limit: usize) -> Vec<SearchHit> {
+    INDEX.search_kind(query, kind, limit)
+}
+
+/// Fold text for comparison; the result is lower case, with diacritics
+/// removed from Latin letters, apostrophes dropped, and any other
+/// punctuation replaced by a single space.
+///
+/// ```
+/// use locale_codes::search::fold;
+///
+/// assert_eq!(fold("Côte d’Ivoire"), "cote divoire");
+/// assert_eq!(fold("Åland Islands"), "aland islands");
+/// assert_eq!(fold("Korea, Republic of"), "korea republic of");
+/// ```
+pub fn fold(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars().flat_map(char::to_lowercase) {
+        match c {
+            '\'' | '’' | 'ʼ' | '`' => {}
+            '\u{0300}'..='\u{036F}' => {}
+            'ß' => folded.push_str("ss"),
+            'æ' => folded.push_str("ae"),
+            'œ' => folded.push_str("oe"),
+            'þ' => folded.push_str("th"),
+            c if c.is_alphanumeric() => folded.push(fold_letter(c)),
+            _ => {
+                if !folded.is_empty() && !folded.ends_with(' ') {
+                    folded.push(' ');
+                }
+            }
+        }
+    }
+    if folded.ends_with(' ') {
+        let _ = folded.pop();
+    }
+    folded
+}
+
+/// Return the Levenshtein distance between two strings, the number of
+/// single character insertions, deletions or substitutions required to
+/// change one into the other.
+///
+/// ```
+/// use locale_codes::search::edit_distance;
+///
+/// assert_eq!(edit_distance("franc", "frank"), 1);
+/// assert_eq!(edit_distance("cyrilic", "cyrillic"), 1);
+/// ```
+pub fn edit_distance(from: &str, to: &str) -> usize {
+    let to: Vec<char> = to.chars().collect();
+    let mut previous: Vec<usize> = (0..=to.len()).collect();
+    let mut current = vec![0; to.len() + 1];
+    for (i, from_c) in from.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, to_c) in to.iter().enumerate() {
+            let substitution = previous[j] + usize::from(from_c != *to_c);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[to.len()]
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for EntryKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "{}",
+            match self {
+                EntryKind::Country => "country",
+                EntryKind::Region => "region",
+                EntryKind::Language => "language",
+                EntryKind::Script => "script",
+                EntryKind::Currency => "currency",
+                EntryKind::Codeset => "codeset",
+            }
+        )
+    }
+}
+
+impl SearchIndex {
+    /// Create an index over all the registries, including the localized
+    /// names for each of the given locales; locales without display names
+    /// are ignored.
+    pub fn new(locales: &[&str]) -> Self {
+        let mut builder = IndexBuilder::default();
+
+        for code in country::all_codes() {
+            let info = country::lookup(&code).unwrap();
+            let entry = builder.entry(EntryKind::Country, &info.short_code, &info.english_name());
+            builder.term(entry, MatchField::Code, &info.short_code);
+            builder.term(entry, MatchField::Code, &info.code);
+            builder.term(entry, MatchField::Name, &info.english_name());
+            builder.localized(entry, NameKind::Territory, &info.short_code, locales);
+        }
+
+        for info in region::aggregate_regions() {
+            let code = format!("{:03}", info.code);
+            let entry = builder.entry(EntryKind::Region, &code, &info.name);
+            builder.term(entry, MatchField::Code, &code);
+            builder.term(entry, MatchField::Name, &info.name);
+            builder.localized(entry, NameKind::Territory, &code, locales);
+        }
+
+        for info in language::iter() {
+            let entry = builder.entry(EntryKind::Language, &info.code, &info.reference_name);
+            builder.term(entry, MatchField::Code, &info.code);
+            for code in [
+                &info.short_code,
+                &info.bibliographic_code,
+                &info.terminology_code,
+            ]
+            .iter()
+            .filter_map(|code| code.as_ref())
+            {
+                builder.term(entry, MatchField::Code, code);
+            }
+            builder.term(entry, MatchField::Name, &info.reference_name);
+            if let Some(name) = &info.indigenous_name {
+                builder.term(entry, MatchField::Alias, name);
+            }
+            for name in info.other_names.iter().flatten() {
+                builder.term(entry, MatchField::Alias, name);
+            }
+            builder.localized(entry, NameKind::Language, &info.name_key().1, locales);
+        }
+
+        for code in script::all_alpha_codes() {
+            let info = script::lookup_by_alpha(&code).unwrap();
+            let entry = builder.entry(EntryKind::Script, &info.alphabetic_code, &info.name);
+            builder.term(entry, MatchField::Code, &info.alphabetic_code);
+            builder.term(entry, MatchField::Name, &info.name);
+            if let Some(alias) = &info.alias {
+                builder.term(entry, MatchField::Alias, alias);
+            }
+            builder.localized(entry, NameKind::Script, &info.alphabetic_code, locales);
+        }
+
+        for code in currency::all_alpha_codes() {
+            let info = currency::lookup_by_alpha(&code).unwrap();
+            let entry = builder.entry(EntryKind::Currency, &info.alphabetic_code, &info.name);
+            builder.term(entry, MatchField::Code, &info.alphabetic_code);
+            builder.term(entry, MatchField::Name, &info.name);
+            builder.localized(entry, NameKind::Currency, &info.alphabetic_code, locales);
+        }
+
+        for name in codeset::all_names() {
+            let info = codeset::lookup(&name).unwrap();
+            let entry = builder.entry(EntryKind::Codeset, &info.name, &info.name);
+            builder.term(entry, MatchField::Code, &info.name);
+            for alias in &info.also_known_as {
+                builder.term(entry, MatchField::Alias, alias);
+            }
+        }
+
+        info!(
+            "SearchIndex::new - indexed {} entries, {} terms",
+            builder.index.entries.len(),
+            builder.index.terms.len()
+        );
+        builder.index
+    }
+
+    /// Return, at most, `limit` hits for the query across all registries,
+    /// best first.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        self.search_filtered(query, None, limit)
+    }
+
+    /// Return, at most, `limit` hits for the query in a single registry,
+    /// best first.
+    pub fn search_kind(&self, query: &str, kind: EntryKind, limit: usize) -> Vec<SearchHit> {
+        self.search_filtered(query, Some(kind), limit)
+    }
+
+    fn search_filtered(
+        &self,
+        query: &str,
+        kind: Option<EntryKind>,
+        limit: usize,
+    ) -> Vec<SearchHit> {
+        debug!("SearchIndex::search: {:?} {:?}", query, kind);
+        let query = Query::new(query);
+        if query.tokens.is_empty() {
+            return Vec::new();
+        }
+
+        // only the best term for each entry is kept.
+        let mut best: HashMap<usize, (f64, &Term)> = HashMap::new();
+        for term in &self.terms {
+            if kind.is_some_and(|kind| self.entries[term.entry].kind != kind) {
+                continue;
+            }
+            if let Some(score) = query.score(term) {
+                let current = best.entry(term.entry).or_insert((score, term));
+                if score > current.0 {
+                    *current = (score, term);
+                }
+            }
+        }
+
+        let mut hits: Vec<SearchHit> = best
+            .into_iter()
+            .map(|(entry, (score, term))| {
+                let entry = &self.entries[entry];
+                SearchHit {
+                    kind: entry.kind,
+                    code: entry.code.clone(),
+                    name: entry.name.clone(),
+                    matched: term.text.clone(),
+                    field: term.field,
+                    score,
+                }
+            })
+            .collect();
+        hits.sort_by(|lhs, rhs| {
+            rhs.score
+                .partial_cmp(&lhs.score)
+                .unwrap_or(Ordering::Equal)
+                .then(lhs.kind.cmp(&rhs.kind))
+                .then(lhs.code.cmp(&rhs.code))
+        });
+        hits.truncate(limit);
+        hits
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct Entry {
+    kind: EntryKind,
+    code: String,
+    name: String,
+}
+
+#[derive(Debug)]
+struct Term {
+    entry: usize,
+    field: MatchField,
+    text: String,
+    folded: String,
+    compact: String,
+    tokens: Vec<String>,
+}
+
+#[derive(Default)]
+struct IndexBuilder {
+    index: SearchIndex,
+    seen: HashSet<(usize, String)>,
+}
+
+struct Query {
+    folded: String,
+    compact: String,
+    tokens: Vec<String>,
+}
+
+impl IndexBuilder {
+    fn entry(&mut self, kind: EntryKind, code: &str, name: &str) -> usize {
+        self.index.entries.push(Entry {
+            kind,
+            code: code.to_string(),
+            name: name.to_string(),
+        });
+        self.index.entries.len() - 1
+    }
+
+    fn term(&mut self, entry: usize, field: MatchField, text: &str) {
+        let folded = fold(text);
+        // the same text is only indexed once for an entry, in the first,
+        // and so most significant, field it was added for.
+        if folded.is_empty() || !self.seen.insert((entry, folded.clone())) {
+            return;
+        }
+        self.index.terms.push(Term {
+            entry,
+            field,
+            text: text.to_string(),
+            compact: compact(&folded),
+            tokens: folded.split(' ').map(str::to_string).collect(),
+            folded,
+        });
+    }
+
+    fn localized(&mut self, entry: usize, kind: NameKind, code: &str, locales: &[&str]) {
+        for locale in locales {
+            if let Some(name) =
+                display_name::names_in_locale(kind, locale).and_then(|names| names.get(code))
+            {
+                self.term(entry, MatchField::LocalizedName, name);
+            }
+        }
+    }
+}
+
+impl Query {
+    fn new(query: &str) -> Self {
+        let folded = fold(query);
+        Query {
+            compact: compact(&folded),
+            tokens: folded
+                .split(' ')
+                .filter(|token| !token.is_empty())
+                .map(str::to_string)
+                .collect(),
+            folded,
+        }
+    }
+
+    fn score(&self, term: &Term) -> Option<f64> {
+        let weight = match term.field {
+            MatchField::Code => {
+                return if self.compact == term.compact {
+                    Some(1.0)
+                } else {
+                    None
+                };
+            }
+            MatchField::Name => 1.0,
+            MatchField::Alias => 0.95,
+            MatchField::LocalizedName => 0.9,
+        };
+        if self.folded == term.folded || self.compact == term.compact {
+            return Some(weight);
+        }
+
+        // every word in the query must match a word in the term, the more
+        // of the term covered the better.
+        let mut total = 0.0;
+        let mut covered: HashSet<usize> = HashSet::new();
+        for token in &self.tokens {
+            let (index, score) = term
+                .tokens
+                .iter()
+                .enumerate()
+                .filter_map(|(index, term_token)| {
+                    token_score(token, term_token).map(|score| (index, score))
+                })
+                .max_by(|lhs, rhs| lhs.1.partial_cmp(&rhs.1).unwrap_or(Ordering::Equal))?;
+            let _ = covered.insert(index);
+            total += score;
+        }
+        let average = total / self.tokens.len() as f64;
+        let coverage = covered.len() as f64 / term.tokens.len() as f64;
+        Some(0.95 * weight * average * (0.7 + 0.3 * coverage))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn make_default_index() -> SearchIndex {
+    let mut locales = display_name::available_locales();
+    locales.sort();
+    let locales: Vec<&str> = locales.iter().map(String::as_str).collect();
+    SearchIndex::new(&locales)
+}
+
+fn token_score(token: &str, term_token: &str) -> Option<f64> {
+    if token == term_token {
+        return Some(1.0);
+    }
+    let length = token.chars().count();
+    if length >= 3 && term_token.starts_with(token) {
+        return Some(0.8);
+    }
+    let allowed = match length {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    };
+    if allowed == 0 || term_token.chars().count().abs_diff(length) > allowed {
+        return None;
+    }
+    match edit_distance(token, term_token) {
+        distance if distance <= allowed => Some(0.9 - 0.15 * distance as f64),
+        _ => None,
+    }
+}
+
+fn compact(folded: &str) -> String {
+    folded.chars().filter(|c| *c != ' ').collect()
+}
+
+fn fold_letter(c: char) -> char {
+    const FOLDS: &[(char, &str)] = &[
+        ('a', "àáâãäåāăąǎạảấầẩẫậắằẳẵặ"),
+        ('c', "çćĉċč"),
+        ('d', "ďđð"),
+        ('e', "èéêëēĕėęěẹẻẽếềểễệ"),
+        ('g', "ĝğġģ"),
+        ('h', "ĥħ"),
+        ('i', "ìíîïĩīĭįıǐỉị"),
+        ('j', "ĵ"),
+        ('k', "ķ"),
+        ('l', "ĺļľŀł"),
+        ('n', "ñńņňŉ"),
+        ('o', "òóôõöøōŏőơǒọỏốồổỗộớờởỡợ"),
+        ('r', "ŕŗř"),
+        ('s', "śŝşšș"),
+        ('t', "ţťŧț"),
+        ('u', "ùúûüũūŭůűųưǔụủứừửữự"),
+        ('w', "ŵ"),
+        ('y', "ýÿŷỳỵỷỹ"),
+        ('z', "źżž"),
+    ];
+    if c.is_ascii() {
+        return c;
+    }
+    FOLDS
+        .iter()
+        .find(|(_, accented)| accented.contains(c))
+        .map_or(c, |(base, _)| *base)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn test_fold() {
+        assert_eq!(fold("  São Tomé & Príncipe "), "sao tome principe");
+        assert_eq!(fold("Straße"), "strasse");
+        assert_eq!(fold("Türkiye"), "turkiye");
+        assert_eq!(fold("Việt Nam"), "viet nam");
+        assert_eq!(fold("ISO_8859-1:1987"), "iso 8859 1 1987");
+        assert_eq!(fold("Россия"), "россия");
+    }
+
+    #[test]
+    fn test_search_registries() {
+        let top = |query: &str| {
+            let hit = search(query, 1).into_iter().next().unwrap();
+            (hit.kind, hit.code)
+        };
+        assert_eq!(top("cote divoire"), (EntryKind::Country, "CI".to_string()));
+        assert_eq!(top("korea south"), (EntryKind::Country, "KR".to_string()));
+        assert_eq!(top("swiss franc"), (EntryKind::Currency, "CHF".to_string()));
+        assert_eq!(top("cyrillic"), (EntryKind::Script, "Cyrl".to_string()));
+        assert_eq!(
+            top("western europe"),
+            (EntryKind::Region, "155".to_string())
+        );
+        assert_eq!(
+            top("latin1"),
+            (EntryKind::Codeset, "ISO_8859-1:1987".to_string())
+        );
+        assert_eq!(top("deu"), (EntryKind::Country, "DE".to_string()));
+        assert_eq!(top("Deutschland"), (EntryKind::Country, "DE".to_string()));
+        assert_eq!(top("allemand"), (EntryKind::Language, "deu".to_string()));
+    }
+
+    #[test]
+    fn test_search_ranking() {
+        let hits = search("cyrilic", 5);
+        assert_eq!(hits[0].code, "Cyrl");
+        assert!(hits[0].score < 1.0);
+        assert!(hits.windows(2).all(|pair| pair[0].score >= pair[1].score));
+
+        let hits = search("JPY", 5);
+        assert_eq!(hits[0].field, MatchField::Code);
+        assert_eq!(hits[0].score, 1.0);
+
+        let hits = search_kind("franc", EntryKind::Currency, 20);
+        assert!(hits.iter().all(|hit| hit.kind == EntryKind::Currency));
+        assert!(hits.iter().any(|hit| hit.code == "CHF"));
+
+        assert!(search("", 5).is_empty());
+        assert!(search("zzzzqqqq", 5).is_empty());
+    }
+
+    #[test]
+    fn test_index_locales() {
+        let index = SearchIndex::new(&[]);
+        assert!(index.search("Deutschland", 1).is_empty());
+        assert_eq!(index.search("germany", 1)[0].code, "DE");
+
+        let index = SearchIndex::new(&["de"]);
+        let hit = &index.search("deutschland", 1)[0];
+        assert_eq!(hit.code, "DE");
+        assert_eq!(hit.field, MatchField::LocalizedName);
+        assert_eq!(hit.name, "Germany");
+    }
+}
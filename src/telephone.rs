@@ -0,0 +1,350 @@
+/*!
+Country calling codes from the international public telecommunication numbering plan.
+
+ITU-T Recommendation E.164 assigns each country a calling code of one to
+three digits that precedes the national significant number when dialing
+internationally, for example `+44 20 7946 0000` for a number in London. Some
+calling codes are shared, notably `+1` which is used by all the members of
+the North American Numbering Plan (NANP) and `+7` which is used by both
+Russia and Kazakhstan; in these cases the national number itself determines
+the country.
+
+Along with the calling code each country defines the prefix used to dial out
+of the country (the international prefix, `00` in most of Europe, `011` in
+the NANP), and the prefix used to dial a national number from within the
+country (the trunk, or national, prefix, commonly `0`).
+
+```
+use locale_codes::telephone;
+
+let uk = telephone::lookup("GB").unwrap();
+assert_eq!(uk.calling_code, 44);
+assert_eq!(uk.national_prefix, Some("0".to_string()));
+
+let countries: Vec<&str> = telephone::countries_for_number("+1 416 555 0199")
+    .iter()
+    .map(|c| c.short_code.as_str())
+    .collect();
+assert_eq!(countries, vec!["CA"]);
+```
+
+## Source - ITU-T E.164
+
+The data used here is taken from the Google
+[libphonenumber](https://github.com/google/libphonenumber) project metadata.
+*/
+
+use std::collections::HashMap;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::country::{self, CountryInfo};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// A representation of the E.164 numbering plan details for a country.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CallingCodeInfo {
+    /// The ISO-3166, part 1, 2-character identifier of the country. This
+    /// may also be one of the exceptionally reserved codes, such as `AC`
+    /// for Ascension Island, or the user-assigned `XK` for Kosovo.
+    pub short_code: String,
+    /// The E.164 country calling code.
+    pub calling_code: u16,
+    /// Where a calling code is shared, `true` for the country that the code
+    /// is primarily associated with, for example the United States for `+1`.
+    pub main_country_for_code: bool,
+    /// Where a calling code is shared, a pattern matching the leading digits
+    /// of national numbers that identify this country, if known.
+    pub leading_digits: Option<String>,
+    /// The prefix, or a pattern for the prefixes, used to dial an
+    /// international number from within this country.
+    pub international_prefix: Option<String>,
+    /// The preferred international prefix where `international_prefix` is a
+    /// pattern; a `~` denotes waiting for a second dial tone.
+    pub preferred_international_prefix: Option<String>,
+    /// The trunk prefix used to dial a national number from within this
+    /// country.
+    pub national_prefix: Option<String>,
+    /// The possible lengths of a national significant number, in ascending
+    /// order.
+    pub national_number_lengths: Vec<u8>,
+    /// Where a calling code is shared, a pattern matching the geographic
+    /// (fixed line and mobile) national numbers for this country.
+    pub national_number_pattern: Option<String>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+lazy_static! {
+    static ref CALLING_CODES: HashMap<String, CallingCodeInfo> = load_calling_codes_from_json();
+    static ref CODE_LOOKUP: HashMap<u16, Vec<String>> = make_calling_code_lookup();
+    static ref PATTERNS: HashMap<String, (Option<Regex>, Option<Regex>)> = make_patterns();
+}
+
+/// Lookup a `CallingCodeInfo` based on a country's ISO-3166 2, or 3,
+/// character identifier, returning `None` if the country has no E.164
+/// calling code.
+pub fn lookup(country_code: &str) -> Option<&'static CallingCodeInfo> {
+    debug!("telephone::lookup: {}", country_code);
+    match country_code.len() {
+        3 => match country::lookup(country_code) {
+            Some(country) => CALLING_CODES.get(&country.short_code),
+            None => None,
+        },
+        _ => CALLING_CODES.get(country_code),
+    }
+}
+
+/// Return all the countries that share the given calling code; the main
+/// country for the code, if any, is first, the remainder are in code order.
+pub fn lookup_by_calling_code(calling_code: u16) -> Vec<&'static CallingCodeInfo> {
+    match CODE_LOOKUP.get(&calling_code) {
+        Some(codes) => codes
+            .iter()
+            .filter_map(|code| CALLING_CODES.get(code))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Return the candidate countries for an international telephone number,
+/// written either with a leading `+`, or with the `00` international prefix.
+/// Spaces and punctuation in the number are ignored. Where the calling code
+/// is shared the national number is used to narrow down the candidates, if
+/// this is not possible all countries sharing the code are returned.
+pub fn lookup_number(number: &str) -> Vec<&'static CallingCodeInfo> {
+    debug!("telephone::lookup_number: {}", number);
+    let (calling_code, national_number) = match split_number(number) {
+        Some(split) => split,
+        None => return Vec::new(),
+    };
+    let candidates = lookup_by_calling_code(calling_code);
+    if candidates.len() < 2 || national_number.is_empty() {
+        return candidates;
+    }
+    let matched: Vec<&'static CallingCodeInfo> = candidates
+        .iter()
+        .filter(|info| info.matches_national_number(&national_number))
+        .cloned()
+        .collect();
+    if matched.is_empty() {
+        candidates
+    } else {
+        matched
+    }
+}
+
+/// Return the candidate `CountryInfo` values for an international telephone
+/// number, as for `lookup_number`; codes that are not ISO countries, such as
+/// `XK`, are omitted.
+pub fn countries_for_number(number: &str) -> Vec<&'static CountryInfo> {
+    lookup_number(number)
+        .iter()
+        .filter_map(|info| info.country())
+        .collect()
+}
+
+/// Return all the known country calling codes.
+pub fn all_calling_codes() -> Vec<u16> {
+    CODE_LOOKUP.keys().cloned().collect()
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl CallingCodeInfo {
+    /// Return the `CountryInfo` for this entry, if it is an ISO country.
+    pub fn country(&self) -> Option<&'static CountryInfo> {
+        country::lookup(&self.short_code)
+    }
+
+    /// The shortest possible national significant number.
+    pub fn min_national_number_length(&self) -> Option<u8> {
+        self.national_number_lengths.first().cloned()
+    }
+
+    /// The longest possible national significant number.
+    pub fn max_national_number_length(&self) -> Option<u8> {
+        self.national_number_lengths.last().cloned()
+    }
+
+    /// Returns `true` if a national significant number of `length` digits
+    /// is possible in this country.
+    pub fn is_possible_length(&self, length: usize) -> bool {
+        self.national_number_lengths
+            .iter()
+            .any(|possible| *possible as usize == length)
+    }
+
+    /// Return the prefix to dial an international number from this country,
+    /// `None` if there is no single prefix.
+    pub fn dialing_prefix(&self) -> Option<&str> {
+        match (
+            &self.preferred_international_prefix,
+            &self.international_prefix,
+        ) {
+            (Some(preferred), _) => Some(preferred),
+            (None, Some(prefix)) if prefix.chars().all(|c| c.is_ascii_digit()) => Some(prefix),
+            _ => None,
+        }
+    }
+
+    fn matches_national_number(&self, national_number: &str) -> bool {
+        match PATTERNS.get(&self.short_code) {
+            Some((leading_digits, pattern)) => {
+                leading_digits
+                    .as_ref()
+                    .is_some_and(|regex| regex.is_match(national_number))
+                    || pattern
+                        .as_ref()
+                        .is_some_and(|regex| regex.is_match(national_number))
+            }
+            None => false,
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn split_number(number: &str) -> Option<(u16, String)> {
+    let number = number.trim();
+    let digits: String = number.chars().filter(|c| c.is_ascii_digit()).collect();
+    let digits = if number.starts_with('+') {
+        digits.as_str()
+    } else {
+        digits.strip_prefix("00")?
+    };
+    for length in 1..=3 {
+        if digits.len() >= length {
+            let calling_code = digits[..length].parse::<u16>().unwrap();
+            if CODE_LOOKUP.contains_key(&calling_code) {
+                return Some((calling_code, digits[length..].to_string()));
+            }
+        }
+    }
+    None
+}
+
+// ------------------------------------------------------------------------------------------------
+// Generated Data
+// ------------------------------------------------------------------------------------------------
+
+fn load_calling_codes_from_json() -> HashMap<String, CallingCodeInfo> {
+    info!("load_calling_codes_from_json - loading JSON");
+    let raw_data = include_bytes!("data/calling_codes.json");
+    let code_map: HashMap<String, CallingCodeInfo> = serde_json::from_slice(raw_data).unwrap();
+    info!(
+        "load_calling_codes_from_json - loaded {} countries",
+        code_map.len()
+    );
+    code_map
+}
+
+fn make_calling_code_lookup() -> HashMap<u16, Vec<String>> {
+    info!("make_calling_code_lookup - create from CALLING_CODES");
+    let mut lookup_map: HashMap<u16, Vec<String>> = HashMap::new();
+    for info in CALLING_CODES.values() {
+        lookup_map
+            .entry(info.calling_code)
+            .or_default()
+            .push(info.short_code.to_string());
+    }
+    for codes in lookup_map.values_mut() {
+        codes.sort_by_key(|code| (!CALLING_CODES[code].main_country_for_code, code.to_string()));
+    }
+    info!(
+        "make_calling_code_lookup - mapped {} calling codes",
+        lookup_map.len()
+    );
+    lookup_map
+}
+
+fn make_patterns() -> HashMap<String, (Option<Regex>, Option<Regex>)> {
+    info!("make_patterns - compile from CALLING_CODES");
+    CALLING_CODES
+        .values()
+        .filter(|info| info.leading_digits.is_some() || info.national_number_pattern.is_some())
+        .map(|info| {
+            (
+                info.short_code.to_string(),
+                (
+                    info.leading_digits
+                        .as_ref()
+                        .map(|pattern| Regex::new(&format!("^(?:{})", pattern)).unwrap()),
+                    info.national_number_pattern
+                        .as_ref()
+                        .map(|pattern| Regex::new(&format!("^(?:{})$", pattern)).unwrap()),
+                ),
+            )
+        })
+        .collect()
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn test_calling_codes() {
+        let codes = all_calling_codes();
+        assert!(!codes.is_empty());
+    }
+
+    #[test]
+    fn test_good_country_code() {
+        let germany = lookup("DEU").unwrap();
+        assert_eq!(germany.calling_code, 49);
+        assert_eq!(germany.dialing_prefix(), Some("00"));
+        assert_eq!(germany.national_prefix, Some("0".to_string()));
+        assert!(germany.is_possible_length(11));
+        assert_eq!(lookup("XK").unwrap().calling_code, 383);
+        assert!(lookup("AQ").is_none());
+    }
+
+    #[test]
+    fn test_shared_calling_code() {
+        let nanp = lookup_by_calling_code(1);
+        assert!(nanp.len() > 20);
+        assert_eq!(nanp[0].short_code, "US");
+        assert_eq!(lookup("US").unwrap().dialing_prefix(), Some("011"));
+        let seven: Vec<&str> = lookup_by_calling_code(7)
+            .iter()
+            .map(|info| info.short_code.as_str())
+            .collect();
+        assert_eq!(seven, vec!["RU", "KZ"]);
+        assert_eq!(lookup("RU").unwrap().dialing_prefix(), Some("8~10"));
+    }
+
+    #[test]
+    fn test_lookup_number() {
+        let codes = |number: &str| -> Vec<&str> {
+            lookup_number(number)
+                .iter()
+                .map(|info| info.short_code.as_str())
+                .collect()
+        };
+        assert_eq!(codes("+44 20 7946 0000"), vec!["GB"]);
+        assert_eq!(codes("0049 30 123456"), vec!["DE"]);
+        assert_eq!(codes("+1 (212) 555-0100"), vec!["US"]);
+        assert_eq!(codes("+1 268 460 1234"), vec!["AG"]);
+        assert_eq!(codes("+7 701 123 4567"), vec!["KZ"]);
+        assert_eq!(codes("+7 495 123 4567"), vec!["RU"]);
+        assert_eq!(codes("+1").len(), lookup_by_calling_code(1).len());
+        assert!(codes("020 7946 0000").is_empty());
+        assert!(codes("+999 123").is_empty());
+    }
+}
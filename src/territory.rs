@@ -0,0 +1,285 @@
+/*!
+Languages spoken in each territory, with population shares and official status.
+
+This links the [`country`](../country/index.html) and
+[`language`](../language/index.html) modules; for each country it records the
+languages spoken there, the percentage of the population that speaks each,
+and whether the language has an official status. A language may be official
+nationally, such as French in Switzerland, or only in a region, such as
+Catalan in Spain.
+
+```
+use locale_codes::territory;
+
+let swiss: Vec<&str> = territory::official_languages("CH")
+    .iter()
+    .map(|language| language.code.as_str())
+    .collect();
+assert_eq!(swiss, vec!["deu", "gsw", "fra", "ita"]);
+
+let portuguese = territory::countries_for_language("pt");
+assert!(portuguese.iter().any(|country| country.short_code == "BR"));
+```
+
+## Source - CLDR
+
+The data used here is taken from the `territoryInfo` data in the
+Unicode [CLDR](http://cldr.unicode.org/) supplemental data.
+*/
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::country::{self, CountryInfo};
+use crate::language::{self, LanguageInfo};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// The official status of a language within a territory.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OfficialStatus {
+    /// An official language of the territory.
+    Official,
+    /// Not official in law, but used as the official language in practice.
+    DeFactoOfficial,
+    /// An official language in a region of the territory.
+    OfficialRegional,
+    /// A recognized, or protected, minority language.
+    OfficialMinority,
+}
+
+/// A representation of a language spoken within a territory.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TerritoryLanguageInfo {
+    /// The ISO-639 identifier of the language, the 2-character identifier
+    /// where one exists.
+    pub language_code: String,
+    /// The percentage of the territory's population that speaks the
+    /// language.
+    pub population_percent: f32,
+    /// The official status of the language, if any.
+    pub official_status: Option<OfficialStatus>,
+}
+
+/// A representation of the languages spoken within a territory.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TerritoryInfo {
+    /// The percentage of the territory's population that is literate.
+    pub literacy_percent: f32,
+    /// The languages spoken, ordered by the number of speakers.
+    pub languages: Vec<TerritoryLanguageInfo>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+lazy_static! {
+    static ref TERRITORIES: HashMap<String, TerritoryInfo> = load_territories_from_json();
+    static ref LANGUAGE_LOOKUP: HashMap<String, Vec<(String, f32)>> = make_language_lookup();
+}
+
+/// Lookup a `TerritoryInfo` based on a country's ISO-3166 2, or 3,
+/// character identifier, returning `None` if there is no language data for
+/// the country.
+pub fn lookup(country_code: &str) -> Option<&'static TerritoryInfo> {
+    debug!("territory::lookup: {}", country_code);
+    TERRITORIES.get(&country::lookup(country_code)?.short_code)
+}
+
+/// Return the languages spoken in the identified country, ordered by the
+/// number of speakers.
+pub fn languages_for_country(country_code: &str) -> Vec<&'static LanguageInfo> {
+    match lookup(country_code) {
+        Some(territory) => territory
+            .languages
+            .iter()
+            .filter_map(|language| language.language())
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Return the official, or de facto official, languages of the identified
+/// country, ordered by the number of speakers; regional and minority
+/// languages are not included.
+pub fn official_languages(country_code: &str) -> Vec<&'static LanguageInfo> {
+    match lookup(country_code) {
+        Some(territory) => territory
+            .languages
+            .iter()
+            .filter(|language| language.is_official())
+            .filter_map(|language| language.language())
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Return the countries in which the language, identified by it's ISO-639
+/// 2, or 3, character identifier, is spoken; ordered by the percentage of
+/// each country's population that speaks it.
+pub fn countries_for_language(language_code: &str) -> Vec<&'static CountryInfo> {
+    let language = match language::lookup(language_code) {
+        Some(language) => language,
+        None => return Vec::new(),
+    };
+    match LANGUAGE_LOOKUP.get(&language.code) {
+        Some(countries) => countries
+            .iter()
+            .filter_map(|(country_code, _)| country::lookup(country_code))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Return all the ISO-3166 2-character country codes with language data.
+pub fn all_codes() -> Vec<String> {
+    TERRITORIES.keys().cloned().collect()
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl TerritoryLanguageInfo {
+    /// Return the `LanguageInfo` for this language.
+    pub fn language(&self) -> Option<&'static LanguageInfo> {
+        language::lookup(&self.language_code)
+    }
+
+    /// Returns `true` if this language is official, or de facto official,
+    /// throughout the territory.
+    pub fn is_official(&self) -> bool {
+        matches!(
+            self.official_status,
+            Some(OfficialStatus::Official) | Some(OfficialStatus::DeFactoOfficial)
+        )
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Generated Data
+// ------------------------------------------------------------------------------------------------
+
+fn load_territories_from_json() -> HashMap<String, TerritoryInfo> {
+    info!("load_territories_from_json - loading JSON");
+    let raw_data = include_bytes!("data/territory_languages.json");
+    let territory_map: HashMap<String, TerritoryInfo> = serde_json::from_slice(raw_data).unwrap();
+    info!(
+        "load_territories_from_json - loaded {} territories",
+        territory_map.len()
+    );
+    territory_map
+}
+
+fn make_language_lookup() -> HashMap<String, Vec<(String, f32)>> {
+    info!("make_language_lookup - create from TERRITORIES");
+    let mut lookup_map: HashMap<String, Vec<(String, f32)>> = HashMap::new();
+    for (country_code, territory) in TERRITORIES.iter() {
+        for language in &territory.languages {
+            if let Some(info) = language.language() {
+                lookup_map
+                    .entry(info.code.to_string())
+                    .or_default()
+                    .push((country_code.to_string(), language.population_percent));
+            }
+        }
+    }
+    for countries in lookup_map.values_mut() {
+        countries.sort_by(|lhs, rhs| rhs.1.partial_cmp(&lhs.1).unwrap().then(lhs.0.cmp(&rhs.0)));
+    }
+    info!(
+        "make_language_lookup - mapped {} languages",
+        lookup_map.len()
+    );
+    lookup_map
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn test_territory_codes() {
+        let codes = all_codes();
+        assert!(!codes.is_empty());
+        for code in codes {
+            for language in &lookup(&code).unwrap().languages {
+                assert!(language.language().is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn test_languages_for_country() {
+        let canada: Vec<&str> = languages_for_country("CAN")
+            .iter()
+            .map(|language| language.code.as_str())
+            .collect();
+        assert_eq!(&canada[..3], &["eng", "fra", "zho"]);
+        assert!(canada.contains(&"iku"));
+        let official: Vec<&str> = official_languages("CA")
+            .iter()
+            .map(|language| language.code.as_str())
+            .collect();
+        assert_eq!(official, vec!["eng", "fra"]);
+        let spain = lookup("ES").unwrap();
+        assert_eq!(
+            spain.languages[0].official_status,
+            Some(OfficialStatus::Official)
+        );
+        assert!(spain
+            .languages
+            .iter()
+            .any(|language| language.official_status == Some(OfficialStatus::OfficialRegional)));
+        assert!(spain.literacy_percent > 90.0);
+        assert!(languages_for_country("AQ").is_empty());
+    }
+
+    #[test]
+    fn test_countries_for_language() {
+        let german: Vec<&str> = countries_for_language("deu")
+            .iter()
+            .map(|country| country.short_code.as_str())
+            .collect();
+        assert_eq!(&german[..3], &["LI", "AT", "DE"]);
+        assert!(german.contains(&"CH"));
+        assert_eq!(countries_for_language("de").len(), german.len());
+        assert!(countries_for_language("tlh").is_empty());
+        let portuguese: Vec<&str> = countries_for_language("pt")
+            .iter()
+            .map(|country| country.short_code.as_str())
+            .collect();
+        for code in ["PT", "BR", "AO", "MZ", "CV", "GW", "ST", "TL", "MO", "GQ"] {
+            assert!(portuguese.contains(&code));
+        }
+    }
+
+    #[test]
+    fn test_every_country_has_territory() {
+        for code in country::all_codes() {
+            assert!(lookup(&code).is_some(), "no territory data for {}", code);
+        }
+        for code in ["LI", "ME", "MC", "SV", "NI", "CV", "TL", "MO", "GQ"] {
+            assert!(!official_languages(code).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_bad_codes() {
+        assert!(lookup("").is_none());
+        assert!(lookup("Switzerland").is_none());
+        assert!(languages_for_country("english").is_empty());
+        assert!(official_languages("CHE1").is_empty());
+        assert!(countries_for_language("english").is_empty());
+        assert!(countries_for_language("").is_empty());
+    }
+}
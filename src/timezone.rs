@@ -0,0 +1,360 @@
+/*!
+Time zones used in each country, from the IANA time zone database.
+
+The IANA time zone database (tzdb) names each timezone after a principal
+location, for example `Europe/Zurich`, and includes tables mapping ISO 3166
+country codes to the timezones in use. A timezone may cover a number of
+countries; `Europe/Zurich` covers Switzerland, Liechtenstein, and the German
+exclave of Büsingen. Where a country has more than one timezone each has a
+comment, such as `Büsingen`, to help a user choose between them.
+
+```
+use locale_codes::timezone;
+
+let zones: Vec<&str> = timezone::timezones_for_country("CH")
+    .iter()
+    .map(|zone| zone.name.as_str())
+    .collect();
+assert_eq!(zones, vec!["Europe/Zurich"]);
+
+let countries: Vec<&str> = timezone::countries_for_timezone("Europe/Zurich")
+    .iter()
+    .map(|country| country.short_code.as_str())
+    .collect();
+assert_eq!(countries, vec!["CH", "DE", "LI"]);
+```
+
+The time zone database is updated several times a year, more often than
+this crate; a [`ZoneTable`](struct.ZoneTable.html) can be loaded from the
+local system's copy of either `zone1970.tab` or `zone.tab` at runtime.
+
+## Source - IANA Time Zone Database
+
+The data used here is taken from the `zone1970.tab` file in the
+[IANA](https://www.iana.org/time-zones) tzdata distribution.
+*/
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Error, ErrorKind, Result};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::country::{self, CountryInfo};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// The directory in which the system time zone database is commonly
+/// installed.
+pub const SYSTEM_ZONEINFO_DIR: &str = "/usr/share/zoneinfo";
+
+/// A representation of a timezone, from a tzdb zone table.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TimeZoneInfo {
+    /// The timezone name, as used in the `TZ` environment variable, for
+    /// example `Europe/Zurich`.
+    pub name: String,
+    /// The ISO-3166, part 1, 2-character identifiers of the countries that
+    /// overlap this timezone; the most populous is first.
+    pub country_codes: Vec<String>,
+    /// The latitude of the timezone's principal location, in decimal
+    /// degrees, positive values are north.
+    pub latitude: f64,
+    /// The longitude of the timezone's principal location, in decimal
+    /// degrees, positive values are east.
+    pub longitude: f64,
+    /// A comment to distinguish this timezone from others in the same
+    /// country, only present if a country has more than one timezone.
+    pub comment: Option<String>,
+}
+
+/// A table of timezones, either the data embedded in this crate or one
+/// loaded at runtime from a tzdb `zone1970.tab`, or `zone.tab`, file.
+#[derive(Debug)]
+pub struct ZoneTable {
+    zones: Vec<TimeZoneInfo>,
+    country_lookup: HashMap<String, Vec<usize>>,
+    name_lookup: HashMap<String, usize>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+lazy_static! {
+    static ref ZONES: ZoneTable = ZoneTable::new(load_timezones_from_json());
+}
+
+/// Lookup a `TimeZoneInfo` based on it's name, returning `None` if the name
+/// is not in the embedded zone table.
+pub fn lookup(name: &str) -> Option<&'static TimeZoneInfo> {
+    ZONES.lookup(name)
+}
+
+/// Return the timezones that overlap the country identified by it's ISO-3166
+/// 2, or 3, character identifier, in the order given by the zone table.
+pub fn timezones_for_country(code: &str) -> Vec<&'static TimeZoneInfo> {
+    ZONES.timezones_for_country(code)
+}
+
+/// Return the countries that overlap the named timezone, the most populous
+/// first.
+pub fn countries_for_timezone(name: &str) -> Vec<&'static CountryInfo> {
+    ZONES.countries_for_timezone(name)
+}
+
+/// Return all the timezone names in the embedded zone table.
+pub fn all_names() -> Vec<String> {
+    ZONES.all_names()
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl ZoneTable {
+    /// Parse the content of a tzdb `zone1970.tab`, or `zone.tab`, file.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut zones: Vec<TimeZoneInfo> = Vec::new();
+        for line in content.lines() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let columns: Vec<&str> = line.split('\t').collect();
+            if columns.len() < 3 {
+                return Err(invalid_data(line));
+            }
+            let (latitude, longitude) = match parse_coordinates(columns[1]) {
+                Some(coordinates) => coordinates,
+                None => return Err(invalid_data(line)),
+            };
+            zones.push(TimeZoneInfo {
+                name: columns[2].to_string(),
+                country_codes: columns[0].split(',').map(str::to_string).collect(),
+                latitude,
+                longitude,
+                comment: columns
+                    .get(3)
+                    .filter(|comment| !comment.is_empty())
+                    .map(|comment| comment.to_string()),
+            });
+        }
+        Ok(ZoneTable::new(zones))
+    }
+
+    /// Load a tzdb `zone1970.tab`, or `zone.tab`, file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        info!("ZoneTable::load - loading {:?}", path.as_ref());
+        ZoneTable::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Load the zone table from the system time zone database, preferring
+    /// `zone1970.tab` and falling back to `zone.tab` if it is not present.
+    pub fn load_system() -> Result<Self> {
+        let directory = Path::new(SYSTEM_ZONEINFO_DIR);
+        match ZoneTable::load(directory.join("zone1970.tab")) {
+            Err(ref e) if e.kind() == ErrorKind::NotFound => {
+                ZoneTable::load(directory.join("zone.tab"))
+            }
+            result => result,
+        }
+    }
+
+    /// Lookup a `TimeZoneInfo` based on it's name.
+    pub fn lookup(&self, name: &str) -> Option<&TimeZoneInfo> {
+        match self.name_lookup.get(name) {
+            Some(index) => Some(&self.zones[*index]),
+            None => None,
+        }
+    }
+
+    /// Return the timezones that overlap the country identified by it's
+    /// ISO-3166 2, or 3, character identifier.
+    pub fn timezones_for_country(&self, code: &str) -> Vec<&TimeZoneInfo> {
+        let country = match country::lookup(code) {
+            Some(country) => country,
+            None => return Vec::new(),
+        };
+        match self.country_lookup.get(&country.short_code) {
+            Some(indices) => indices.iter().map(|index| &self.zones[*index]).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Return the countries that overlap the named timezone.
+    pub fn countries_for_timezone(&self, name: &str) -> Vec<&'static CountryInfo> {
+        match self.lookup(name) {
+            Some(zone) => zone.countries(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Return all the timezone names in this table.
+    pub fn all_names(&self) -> Vec<String> {
+        self.zones
+            .iter()
+            .map(|zone| zone.name.to_string())
+            .collect()
+    }
+
+    fn new(zones: Vec<TimeZoneInfo>) -> Self {
+        let mut country_lookup: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut name_lookup: HashMap<String, usize> = HashMap::new();
+        for (index, zone) in zones.iter().enumerate() {
+            for code in &zone.country_codes {
+                country_lookup
+                    .entry(code.to_string())
+                    .or_default()
+                    .push(index);
+            }
+            name_lookup.insert(zone.name.to_string(), index);
+        }
+        info!(
+            "ZoneTable::new - mapped {} timezones, {} countries",
+            zones.len(),
+            country_lookup.len()
+        );
+        ZoneTable {
+            zones,
+            country_lookup,
+            name_lookup,
+        }
+    }
+}
+
+impl TimeZoneInfo {
+    /// Return the `CountryInfo` for each country this timezone overlaps.
+    pub fn countries(&self) -> Vec<&'static CountryInfo> {
+        self.country_codes
+            .iter()
+            .filter_map(|code| country::lookup(code))
+            .collect()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn invalid_data(line: &str) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("invalid zone table line: {:?}", line),
+    )
+}
+
+fn parse_coordinates(coordinates: &str) -> Option<(f64, f64)> {
+    let split = coordinates.rfind(['+', '-'])?;
+    Some((
+        parse_coordinate(&coordinates[..split], 2)?,
+        parse_coordinate(&coordinates[split..], 3)?,
+    ))
+}
+
+fn parse_coordinate(coordinate: &str, degree_digits: usize) -> Option<f64> {
+    let (sign, digits) = match coordinate.chars().next()? {
+        '+' => (1.0, &coordinate[1..]),
+        '-' => (-1.0, &coordinate[1..]),
+        _ => return None,
+    };
+    if !(digits.len() == degree_digits + 2 || digits.len() == degree_digits + 4)
+        || !digits.chars().all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let degrees: f64 = digits[..degree_digits].parse().ok()?;
+    let minutes: f64 = digits[degree_digits..degree_digits + 2].parse().ok()?;
+    let seconds: f64 = match digits.len() - degree_digits {
+        4 => digits[degree_digits + 2..].parse().ok()?,
+        _ => 0.0,
+    };
+    Some(sign * (degrees + minutes / 60.0 + seconds / 3600.0))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Generated Data
+// ------------------------------------------------------------------------------------------------
+
+fn load_timezones_from_json() -> Vec<TimeZoneInfo> {
+    info!("load_timezones_from_json - loading JSON");
+    let raw_data = include_bytes!("data/timezones.json");
+    let zones: Vec<TimeZoneInfo> = serde_json::from_slice(raw_data).unwrap();
+    info!(
+        "load_timezones_from_json - loaded {} timezones",
+        zones.len()
+    );
+    zones
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn test_timezone_names() {
+        let names = all_names();
+        assert!(!names.is_empty());
+    }
+
+    #[test]
+    fn test_timezones_for_country() {
+        let germany: Vec<&str> = timezones_for_country("DEU")
+            .iter()
+            .map(|zone| zone.name.as_str())
+            .collect();
+        assert_eq!(germany, vec!["Europe/Zurich", "Europe/Berlin"]);
+        let zurich = lookup("Europe/Zurich").unwrap();
+        assert_eq!(zurich.comment, Some("Büsingen".to_string()));
+        assert!((zurich.latitude - 47.383333).abs() < 0.0001);
+        assert!((zurich.longitude - 8.533333).abs() < 0.0001);
+        assert!(timezones_for_country("US").len() > 20);
+        assert!(timezones_for_country("XX").is_empty());
+        assert!(timezones_for_country("").is_empty());
+        assert!(timezones_for_country("Switzerland").is_empty());
+    }
+
+    #[test]
+    fn test_countries_for_timezone() {
+        let countries: Vec<&str> = countries_for_timezone("Asia/Dubai")
+            .iter()
+            .map(|country| country.short_code.as_str())
+            .collect();
+        assert_eq!(countries, vec!["AE", "OM", "RE", "SC", "TF"]);
+        assert!(countries_for_timezone("Mars/Olympus_Mons").is_empty());
+    }
+
+    #[test]
+    fn test_parse_zone_table() {
+        let table = ZoneTable::parse(
+            "# zone.tab\nCH\t+4723+00832\tEurope/Zurich\nLI\t+4709+00931\tEurope/Vaduz\n\
+             AQ\t-775048+1663648\tAntarctica/McMurdo\tNew Zealand time - McMurdo, South Pole\n",
+        )
+        .unwrap();
+        let vaduz = table.timezones_for_country("LIE");
+        assert_eq!(vaduz.len(), 1);
+        assert_eq!(vaduz[0].name, "Europe/Vaduz");
+        let mcmurdo = table.lookup("Antarctica/McMurdo").unwrap();
+        assert!((mcmurdo.latitude + 77.846667).abs() < 0.0001);
+        assert!((mcmurdo.longitude - 166.613333).abs() < 0.0001);
+        assert!(mcmurdo.comment.is_some());
+        assert!(ZoneTable::parse("CH\t4723+00832\tEurope/Zurich").is_err());
+        assert!(ZoneTable::parse("CH\tEurope/Zurich").is_err());
+    }
+
+    #[test]
+    fn test_load_system_zone_table() {
+        if Path::new(SYSTEM_ZONEINFO_DIR).join("zone.tab").exists() {
+            let table = ZoneTable::load(Path::new(SYSTEM_ZONEINFO_DIR).join("zone.tab")).unwrap();
+            assert!(!table.timezones_for_country("CH").is_empty());
+        }
+        assert!(ZoneTable::load("/does/not/exist/zone.tab").is_err());
+    }
+}
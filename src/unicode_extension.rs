@@ -0,0 +1,494 @@
+/*!
+Keys and types of the Unicode locale extension to BCP 47 language tags.
+
+The Unicode locale extension, introduced by the `-u-` singleton in a
+language tag, carries user preferences as a set of key and type pairs. For
+example `ar-EG-u-ca-islamic-nu-latn` asks for the Islamic calendar with
+Latin digits, and `en-GB-u-fw-mon-hc-h23` for weeks starting on Monday with
+a 24-hour clock. The valid keys, and for most keys the valid types, are
+defined by the Unicode CLDR; some keys take codes from other registries:
+
+* `cu`, a currency, is validated against the [`currency`](../currency/index.html)
+  registry,
+* `rg`, a region override, and `sd`, a subdivision, are validated against
+  the [`country`](../country/index.html) registry,
+* `dx` and `kr` take script codes, which are validated against the
+  [`script`](../script/index.html) registry.
+
+```
+use locale_codes::unicode_extension::{self, UnicodeExtension};
+
+let extension = UnicodeExtension::parse("ar-EG-u-ca-islamic-umalqura-nu-latn-cu-usd").unwrap();
+assert_eq!(extension.calendar(), Some("islamic-umalqura"));
+assert_eq!(extension.numbering_system(), Some("latn"));
+assert_eq!(extension.currency().unwrap().alphabetic_code, "USD");
+assert!(extension.is_valid());
+
+let key = unicode_extension::lookup("hc").unwrap();
+assert_eq!(key.description, "Hour cycle");
+assert!(unicode_extension::is_valid("hc", "h23"));
+assert!(!unicode_extension::is_valid("hc", "h25"));
+assert!(!unicode_extension::is_valid("cu", "xyz"));
+```
+
+## Source - CLDR
+
+The data used here is taken from the key and type definitions in the
+`common/bcp47` directory of the Unicode [CLDR](http://cldr.unicode.org/).
+*/
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use serde::{Deserialize, Serialize};
+
+use crate::country::{self, CountryInfo};
+use crate::currency::{self, CurrencyInfo};
+use crate::likely_subtags::normalize_script;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// The kind of value a key takes.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ValueType {
+    /// One of the types listed for the key.
+    Enumerated,
+    /// An ISO-4217 currency code, in lower case.
+    Currency,
+    /// A region code followed by `zzzz`, such as `gbzzzz`, or a subdivision
+    /// code.
+    Region,
+    /// A subdivision code, a region code followed by one to four letters or
+    /// digits, such as `gbsct`.
+    Subdivision,
+    /// One or more ISO-15924 script codes.
+    Script,
+    /// One or more ISO-15924 script codes, or listed reorder codes.
+    Reorder,
+    /// A CLDR short time zone identifier, such as `usnyc`.
+    TimeZone,
+}
+
+/// A key defined for the Unicode locale extension.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct KeyInfo {
+    /// The 2-character key.
+    pub key: String,
+    /// A description of the key, in English.
+    pub description: String,
+    /// The kind of value the key takes.
+    pub value_type: ValueType,
+    /// The types listed for the key; for an `Enumerated`, or `TimeZone`, key
+    /// these are the valid types.
+    pub types: Vec<String>,
+    /// Aliases for types, such as `gregorian` for `gregory`, mapped to the
+    /// type.
+    pub aliases: HashMap<String, String>,
+    /// The listed types that are deprecated.
+    pub deprecated: Vec<String>,
+}
+
+/// The reason a keyword in an extension is not valid, or not recommended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeywordIssueKind {
+    /// The key is not defined.
+    UnknownKey,
+    /// The type is not valid for the key.
+    InvalidType,
+    /// The type is valid, but deprecated.
+    DeprecatedType,
+}
+
+/// A keyword in an extension that is not valid, or not recommended.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeywordIssue {
+    /// The key, as written.
+    pub key: String,
+    /// The type, as written.
+    pub value: String,
+    /// The reason for the issue.
+    pub kind: KeywordIssueKind,
+}
+
+/// The keywords of a Unicode locale extension.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UnicodeExtension {
+    /// The keywords, mapping each key to its type; a key without a type has
+    /// the type `true`.
+    pub keywords: BTreeMap<String, String>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+lazy_static! {
+    static ref KEYS: HashMap<String, KeyInfo> = load_keys_from_json();
+}
+
+/// Lookup a `KeyInfo` based on it's 2-character key, returning `None` if
+/// the key is not defined.
+pub fn lookup(key: &str) -> Option<&'static KeyInfo> {
+    debug!("unicode_extension::lookup: {}", key);
+    KEYS.get(&key.to_ascii_lowercase())
+}
+
+/// Return all the defined keys.
+pub fn all_keys() -> Vec<String> {
+    KEYS.keys().cloned().collect()
+}
+
+/// Returns `true` if the type is valid for the key; deprecated types and
+/// aliases are valid.
+pub fn is_valid(key: &str, value: &str) -> bool {
+    lookup(key).is_some_and(|info| info.is_valid_type(value))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for UnicodeExtension {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "u")?;
+        for (key, value) in &self.keywords {
+            if value == "true" {
+                write!(f, "-{}", key)?;
+            } else {
+                write!(f, "-{}-{}", key, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl KeyInfo {
+    /// Returns `true` if the type is valid for this key; deprecated types
+    /// and aliases are valid.
+    pub fn is_valid_type(&self, value: &str) -> bool {
+        self.canonical_type(value).is_some()
+    }
+
+    /// Returns `true` if the type is listed as deprecated for this key.
+    pub fn is_deprecated_type(&self, value: &str) -> bool {
+        self.deprecated.contains(&value.to_ascii_lowercase())
+    }
+
+    /// Return the type in its canonical form, lower case and with any alias
+    /// replaced, `None` if the type is not valid for this key.
+    pub fn canonical_type(&self, value: &str) -> Option<String> {
+        let value = value.to_ascii_lowercase();
+        let is_valid = match self.value_type {
+            ValueType::Enumerated => {
+                if let Some(value) = self.aliases.get(&value) {
+                    return Some(value.to_string());
+                }
+                self.types.contains(&value)
+            }
+            ValueType::Currency => {
+                value.len() == 3
+                    && value.chars().all(|c| c.is_ascii_alphabetic())
+                    && currency::lookup_by_alpha(&value.to_ascii_uppercase()).is_some()
+            }
+            ValueType::Region => {
+                (value.len() == 6 && value.ends_with("zzzz") && is_country(&value[..2]))
+                    || is_subdivision(&value)
+            }
+            ValueType::Subdivision => is_subdivision(&value),
+            ValueType::Script => value
+                .split('-')
+                .all(|script| normalize_script(script).is_some()),
+            ValueType::Reorder => value.split('-').all(|code| {
+                self.types.iter().any(|t| *t == code) || normalize_script(code).is_some()
+            }),
+            ValueType::TimeZone => self.types.contains(&value),
+        };
+        if is_valid {
+            Some(value)
+        } else {
+            None
+        }
+    }
+}
+
+impl UnicodeExtension {
+    /// Parse the Unicode locale extension from either a complete language
+    /// tag, `en-US-u-ca-buddhist`, or the extension alone, `u-ca-buddhist`.
+    /// Returns `None` if there is no extension or it is not well-formed; the
+    /// keys and types are not validated.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let subtags: Vec<&str> = tag.split(['-', '_']).collect();
+        let start = subtags
+            .iter()
+            .position(|subtag| subtag.eq_ignore_ascii_case("u"))?;
+        let rest = &subtags[start + 1..];
+        let end = rest
+            .iter()
+            .position(|subtag| subtag.len() == 1)
+            .unwrap_or(rest.len());
+        Some(UnicodeExtension {
+            keywords: parse_keywords(&rest[..end])?,
+        })
+    }
+
+    /// Construct an extension from a set of keywords.
+    pub fn from_keywords(keywords: BTreeMap<String, String>) -> Self {
+        UnicodeExtension { keywords }
+    }
+
+    /// Return the type for the given key, if present.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.keywords.get(key).map(|value| value.as_str())
+    }
+
+    /// Return the keywords that are not defined, have a type that is not
+    /// valid for the key, or have a deprecated type.
+    pub fn issues(&self) -> Vec<KeywordIssue> {
+        self.keywords
+            .iter()
+            .filter_map(|(key, value)| {
+                let kind = match lookup(key) {
+                    None => KeywordIssueKind::UnknownKey,
+                    Some(info) if !info.is_valid_type(value) => KeywordIssueKind::InvalidType,
+                    Some(info) if info.is_deprecated_type(value) => {
+                        KeywordIssueKind::DeprecatedType
+                    }
+                    Some(_) => return None,
+                };
+                Some(KeywordIssue {
+                    key: key.clone(),
+                    value: value.clone(),
+                    kind,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns `true` if every key is defined and every type is valid for
+    /// its key; deprecated types are valid.
+    pub fn is_valid(&self) -> bool {
+        self.issues()
+            .iter()
+            .all(|issue| issue.kind == KeywordIssueKind::DeprecatedType)
+    }
+
+    /// Return the calendar, the `ca` key, in canonical form; `None` if not
+    /// present or not valid.
+    pub fn calendar(&self) -> Option<&'static str> {
+        self.enumerated_type("ca")
+    }
+
+    /// Return the numbering system, the `nu` key; `None` if not present or
+    /// not valid.
+    pub fn numbering_system(&self) -> Option<&'static str> {
+        self.enumerated_type("nu")
+    }
+
+    /// Return the first day of the week, the `fw` key; `None` if not
+    /// present or not valid.
+    pub fn first_weekday(&self) -> Option<&'static str> {
+        self.enumerated_type("fw")
+    }
+
+    /// Return the hour cycle, the `hc` key; `None` if not present or not
+    /// valid.
+    pub fn hour_cycle(&self) -> Option<&'static str> {
+        self.enumerated_type("hc")
+    }
+
+    /// Return the `CurrencyInfo` for the currency override, the `cu` key;
+    /// `None` if not present or not valid.
+    pub fn currency(&self) -> Option<&'static CurrencyInfo> {
+        let value = self.get("cu")?;
+        if value.len() == 3 && value.chars().all(|c| c.is_ascii_alphabetic()) {
+            currency::lookup_by_alpha(&value.to_ascii_uppercase())
+        } else {
+            None
+        }
+    }
+
+    /// Return the `CountryInfo` for the region override, the `rg` key;
+    /// `None` if not present or not valid.
+    pub fn region_override(&self) -> Option<&'static CountryInfo> {
+        let value = self.get("rg")?.to_ascii_lowercase();
+        if lookup("rg")?.is_valid_type(&value) {
+            country::lookup(&value[..2].to_ascii_uppercase())
+        } else {
+            None
+        }
+    }
+
+    /// Return the `CountryInfo` for the subdivision, the `sd` key; `None`
+    /// if not present or not valid.
+    pub fn subdivision_country(&self) -> Option<&'static CountryInfo> {
+        let value = self.get("sd")?.to_ascii_lowercase();
+        if is_subdivision(&value) {
+            country::lookup(&value[..2].to_ascii_uppercase())
+        } else {
+            None
+        }
+    }
+
+    fn enumerated_type(&self, key: &str) -> Option<&'static str> {
+        let info = lookup(key)?;
+        let value = info.canonical_type(self.get(key)?)?;
+        info.types.iter().find(|t| **t == value).map(|t| t.as_str())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+/// Parse the subtags following the `u` singleton into keywords, returning
+/// `None` if they are not well-formed. Extension attributes are not
+/// supported, and where a key is repeated the first is used.
+pub(crate) fn parse_keywords(subtags: &[&str]) -> Option<BTreeMap<String, String>> {
+    let mut keywords = BTreeMap::new();
+    let mut key: Option<String> = None;
+    let mut types: Vec<String> = Vec::new();
+    for subtag in subtags {
+        let subtag = subtag.to_ascii_lowercase();
+        if is_key(&subtag) {
+            if let Some(key) = key.replace(subtag) {
+                insert_keyword(&mut keywords, key, &types);
+                types.clear();
+            }
+        } else if key.is_some() && is_type(&subtag) {
+            types.push(subtag);
+        } else {
+            return None;
+        }
+    }
+    insert_keyword(&mut keywords, key?, &types);
+    Some(keywords)
+}
+
+/// Returns `true` if the subtag is a well-formed key.
+pub(crate) fn is_key(subtag: &str) -> bool {
+    let bytes = subtag.as_bytes();
+    bytes.len() == 2 && bytes[0].is_ascii_alphanumeric() && bytes[1].is_ascii_alphabetic()
+}
+
+/// Returns `true` if the value is a well-formed type, one or more subtags
+/// of 3 to 8 letters or digits.
+pub(crate) fn is_type(value: &str) -> bool {
+    value.split('-').all(|subtag| {
+        (3..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+    })
+}
+
+fn insert_keyword(keywords: &mut BTreeMap<String, String>, key: String, types: &[String]) {
+    let value = if types.is_empty() {
+        "true".to_string()
+    } else {
+        types.join("-")
+    };
+    keywords.entry(key).or_insert(value);
+}
+
+fn is_country(code: &str) -> bool {
+    code.len() == 2
+        && code.chars().all(|c| c.is_ascii_alphabetic())
+        && country::lookup(&code.to_ascii_uppercase()).is_some()
+}
+
+fn is_subdivision(value: &str) -> bool {
+    (3..=6).contains(&value.len())
+        && value.is_ascii()
+        && is_country(&value[..2])
+        && value[2..].chars().all(|c| c.is_ascii_alphanumeric())
+        && &value[2..] != "zzzz"
+}
+
+// ------------------------------------------------------------------------------------------------
+// Generated Data
+// ------------------------------------------------------------------------------------------------
+
+fn load_keys_from_json() -> HashMap<String, KeyInfo> {
+    info!("load_keys_from_json - loading JSON");
+    let raw_data = include_bytes!("data/unicode_extension_keys.json");
+    let key_map: HashMap<String, KeyInfo> = serde_json::from_slice(raw_data).unwrap();
+    info!("load_keys_from_json - loaded {} keys", key_map.len());
+    key_map
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn test_key_types() {
+        assert_eq!(lookup("ca").unwrap().value_type, ValueType::Enumerated);
+        assert!(lookup("xx").is_none());
+        assert!(is_valid("ca", "islamic-umalqura"));
+        assert!(is_valid("ca", "gregorian"));
+        assert!(is_valid("nu", "arab"));
+        assert!(is_valid("fw", "mon"));
+        assert!(!is_valid("fw", "monday"));
+        assert!(is_valid("cu", "eur"));
+        assert!(!is_valid("cu", "euro"));
+        assert!(is_valid("rg", "gbzzzz"));
+        assert!(is_valid("rg", "gbsct"));
+        assert!(!is_valid("rg", "xxzzzz"));
+        assert!(is_valid("sd", "usca"));
+        assert!(!is_valid("sd", "uszzzz"));
+        assert!(is_valid("dx", "thai-laoo"));
+        assert!(!is_valid("dx", "thai-xxxx"));
+        assert!(is_valid("kr", "grek-digit"));
+        assert!(is_valid("tz", "usnyc"));
+        assert!(is_valid("tz", "utce01"));
+        assert!(!is_valid("tz", "qqqqqq"));
+        assert!(!is_valid("tz", "usnyc-gblon"));
+        assert!(lookup("tz").unwrap().is_deprecated_type("cnckg"));
+        let calendar = lookup("ca").unwrap();
+        assert_eq!(
+            calendar.canonical_type("Gregorian"),
+            Some("gregory".to_string())
+        );
+        assert!(lookup("co").unwrap().is_deprecated_type("direct"));
+    }
+
+    #[test]
+    fn test_extension() {
+        let extension =
+            UnicodeExtension::parse("en-GB-u-fw-mon-hc-h23-ca-islamicc-rg-uszzzz-x-private")
+                .unwrap();
+        assert_eq!(extension.calendar(), Some("islamic-civil"));
+        assert_eq!(extension.first_weekday(), Some("mon"));
+        assert_eq!(extension.hour_cycle(), Some("h23"));
+        assert_eq!(extension.region_override().unwrap().short_code, "US");
+        assert!(extension.currency().is_none());
+        assert!(extension.is_valid());
+        assert_eq!(
+            extension.to_string(),
+            "u-ca-islamicc-fw-mon-hc-h23-rg-uszzzz"
+        );
+
+        let extension = UnicodeExtension::parse("u-co-direct-cu-xyz-zz-abc-kn").unwrap();
+        assert_eq!(extension.get("kn"), Some("true"));
+        assert_eq!(
+            extension
+                .issues()
+                .iter()
+                .map(|issue| (issue.key.as_str(), issue.kind))
+                .collect::<Vec<(&str, KeywordIssueKind)>>(),
+            vec![
+                ("co", KeywordIssueKind::DeprecatedType),
+                ("cu", KeywordIssueKind::InvalidType),
+                ("zz", KeywordIssueKind::UnknownKey),
+            ]
+        );
+        assert!(!extension.is_valid());
+        assert!(UnicodeExtension::parse("en-US").is_none());
+        assert!(UnicodeExtension::parse("en-u-attr").is_none());
+    }
+}